@@ -0,0 +1,73 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+
+use glifparser::pedantry::{Level, Mend};
+use glifparser::{Glif, Pedantry};
+
+use std::fs;
+
+#[path = "read/xmltree_reader.rs"]
+mod xmltree_reader;
+
+fn test_data_glifs() -> Vec<String> {
+    let mut glifs: Vec<_> = ["test_data/TT2020Base.ufo/glyphs", "test_data/bugfixes"]
+        .iter()
+        .flat_map(|dir| fs::read_dir(dir).unwrap())
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().map(|e| e == "glif").unwrap_or(false))
+        .collect();
+    glifs.sort();
+    glifs.into_iter().map(|path| fs::read_to_string(path).unwrap()).collect()
+}
+
+/// A glyph with `contours` contours of `points` cubic points each, plus the usual trimmings.
+fn large_glif(contours: usize, points: usize) -> String {
+    let mut ret = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<glyph name=\"large\" format=\"2\">\n  <advance width=\"1000\"/>\n  <unicode hex=\"4E00\"/>\n  <anchor x=\"500\" y=\"800\" name=\"top\"/>\n  <outline>\n");
+    for c in 0..contours {
+        ret.push_str("    <contour>\n");
+        for p in 0..points {
+            let (x, y) = (c * 10 + p, p * 7 % 1000);
+            ret.push_str(&format!("      <point x=\"{}\" y=\"{}\"/>\n      <point x=\"{}.5\" y=\"{}\"/>\n      <point x=\"{}\" y=\"{}\" type=\"curve\" smooth=\"yes\"/>\n", x, y, x + 1, y + 2, x + 3, y + 4));
+        }
+        ret.push_str("    </contour>\n");
+    }
+    ret.push_str("    <component base=\"a\" xOffset=\"10\"/>\n  </outline>\n  <lib>\n    <dict>\n      <key>public.markColor</key>\n      <string>1,0,0,1</string>\n    </dict>\n  </lib>\n</glyph>\n");
+    ret
+}
+
+fn bench_read(c: &mut Criterion) {
+    let glifs = test_data_glifs();
+    let mut group = c.benchmark_group("read");
+    group.throughput(Throughput::Bytes(glifs.iter().map(|g| g.len() as u64).sum()));
+    // Some are broken on purpose, so errors count too
+    group.bench_function("test_data", |b| b.iter(|| {
+        for glif in glifs.iter() {
+            let _: Result<Glif<()>, _> = glifparser::glif::read(glif);
+        }
+    }));
+    group.bench_function("test_data_xmltree_reader", |b| b.iter(|| {
+        for glif in glifs.iter() {
+            let _: Result<Glif<()>, _> = xmltree_reader::read_ufo_glif(glif);
+        }
+    }));
+    for (contours, points) in [(10, 10), (100, 50)] {
+        let glif = large_glif(contours, points);
+        group.throughput(Throughput::Bytes(glif.len() as u64));
+        group.bench_with_input(BenchmarkId::new("large", contours * points * 3), &glif, |b, glif| b.iter(|| {
+            let _: Glif<()> = glifparser::glif::read(glif).unwrap();
+        }));
+        group.bench_with_input(BenchmarkId::new("scan", contours * points * 3), &glif, |b, glif| b.iter(|| {
+            glifparser::glif::scan(glif).unwrap()
+        }));
+        group.bench_with_input(BenchmarkId::new("large_xmltree_reader", contours * points * 3), &glif, |b, glif| b.iter(|| {
+            let _: Glif<()> = xmltree_reader::read_ufo_glif(glif).unwrap();
+        }));
+        // Every off-curve point is a float, so each is rounded and reported as a mend
+        group.bench_with_input(BenchmarkId::new("large_mended", contours * points * 3), &glif, |b, glif| b.iter(|| {
+            let _: (Glif<()>, _) = glifparser::glif::read_with_mends(glif, Pedantry::new(Level::TrueType, Mend::Always)).unwrap();
+        }));
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_read);
+criterion_main!(benches);
@@ -0,0 +1,222 @@
+//! The xmltree-based reader which glifparser 2.0.1 shipped, kept only to benchmark the streaming
+//! reader against. It's ported to the public API, but otherwise reads as it did: a DOM is built,
+//! then each element is taken out of it with `take_child`.
+
+use std::convert::TryInto;
+use std::path;
+
+use integer_or_float::IntegerOrFloat;
+
+use glifparser::anchor::{Anchor, GlifAnchor};
+use glifparser::error::GlifParserError::{self, GlifInputError};
+#[cfg(feature = "glifserde")]
+use glifparser::glif::Lib;
+use glifparser::guideline::Guideline;
+#[cfg(feature = "glifimage")]
+use glifparser::image::GlifImage;
+use glifparser::outline::{GlifContour, GlifOutline, Outline};
+use glifparser::point::{GlifPoint, IsValid as _, PointData, PointType};
+use glifparser::string::GlifStringLenOne;
+use glifparser::{Glif, GlifComponent, Pedantry};
+
+macro_rules! input_error {
+    ($str:expr) => {
+        GlifInputError($str.to_string())
+    }
+}
+
+macro_rules! load_matrix_and_identifier {
+    ($xml_el:ident, $struct:ident, ($($attr:ident),+)) => {
+        $(
+            if let Some(e) = $xml_el.attributes.get(stringify!($attr)) {
+                $struct.$attr = e.as_str().try_into().or(Err(input_error!(concat!("Matrix member ", stringify!($attr), " not float"))))?;
+            }
+        )+
+        $struct.identifier = $xml_el.attributes.get("identifier").cloned();
+    }
+}
+
+pub fn read_ufo_glif<PD: PointData>(glif: &str) -> Result<Glif<PD>, GlifParserError> {
+    let pedantry = Pedantry::default();
+    let mut glif = xmltree::Element::parse(glif.as_bytes())?;
+
+    let mut ret = Glif::new();
+
+    if glif.name != "glyph" {
+        return Err(input_error!("Root element not <glyph>"))
+    }
+
+    if glif.attributes.get("format").ok_or(input_error!("no format in <glyph>"))? != "2" {
+        return Err(input_error!("<glyph> format not 2"))
+    }
+
+    ret.name = glif
+        .attributes
+        .get("name")
+        .ok_or(input_error!("<glyph> has no name"))?
+        .clone();
+    ret.components.root = ret.name.clone();
+
+    ret.width = if let Some(a) = glif.take_child("advance") {
+        let width = a.attributes
+            .get("width")
+            .ok_or(input_error!("<advance> has no width"))?;
+        match width.parse::<u64>() {
+            Err(_) => if let Ok((f, false)) = width.parse::<f32>().map(|f|(f, f.is_subnormal())) {
+                if !pedantry.should_mend() {
+                    Err(input_error!("Floating point value given as <advance> width"))?
+                }
+                Some(f as u64)
+            } else {
+                Err(input_error!("<advance> width neither int nor downgradable (not subnormal) float!"))?
+            },
+            Ok(i) => Some(i)
+        }
+    } else {
+        None
+    };
+
+    while let Some(u) = glif.take_child("unicode") {
+        let unicodehex = u
+            .attributes
+            .get("hex")
+            .ok_or(input_error!("<unicode> has no hex"))?;
+        ret.unicode.push(
+            char::from_u32(
+                u32::from_str_radix(unicodehex, 16)
+                .or(Err(input_error!("<unicode> hex not int")))?
+            )
+            .ok_or(input_error!("<unicode> char conversion failed"))?,
+        );
+    }
+
+    while let Some(anchor_el) = glif.take_child("anchor") {
+        let x = anchor_el
+            .attributes
+            .get("x")
+            .ok_or(input_error!("<anchor> missing x"))?
+            .parse()
+            .or(Err(input_error!("<anchor> x not integer/float")))?;
+        let y = anchor_el
+            .attributes
+            .get("y")
+            .ok_or(input_error!("<anchor> missing y"))?
+            .parse()
+            .or(Err(input_error!("<anchor> y not integer/float")))?;
+        let class = anchor_el
+            .attributes
+            .get("name")
+            .and_then(|a|GlifStringLenOne::try_from(a.clone()).ok());
+        let anchor = GlifAnchor::new(x, y, class);
+        ret.anchors.push(Anchor::from_glif(&anchor, pedantry)?);
+    }
+
+    #[cfg(feature = "glifimage")]
+    while let Some(image_el) = glif.take_child("image") {
+        let filename = path::PathBuf::from(image_el
+            .attributes
+            .get("fileName")
+            .ok_or(input_error!("<image> missing fileName"))?);
+        let mut gimage = GlifImage::from_filename(filename)?;
+        load_matrix_and_identifier!(image_el, gimage, (xScale, xyScale, yxScale, yScale, xOffset, yOffset));
+        if let Some(color) = image_el.attributes.get("color") {
+            gimage.color = Some(color.parse()?);
+        }
+        ret.images.push(gimage);
+    }
+
+    while let Some(guideline_el) = glif.take_child("guideline") {
+        let gx = guideline_el
+            .attributes
+            .get("x")
+            .ok_or(input_error!("<guideline> missing x"))?
+            .parse()
+            .or(Err(input_error!("<guideline> x not float")))?;
+        let gy = guideline_el
+            .attributes
+            .get("y")
+            .ok_or(input_error!("<guideline> missing y"))?
+            .parse()
+            .or(Err(input_error!("<guideline> x not float")))?;
+        let angle: IntegerOrFloat = guideline_el
+            .attributes
+            .get("angle")
+            .ok_or(input_error!("<guideline> missing angle"))?
+            .as_str()
+            .try_into()
+            .or(Err(input_error!("<guideline> angle not float")))?;
+        let mut guideline = Guideline::from_x_y_angle(gx, gy, angle);
+        if let Some(color) = guideline_el.attributes.get("color") {
+            guideline.color = Some(color.parse()?);
+        }
+        guideline.name = guideline_el.attributes.get("name").cloned();
+        guideline.identifier = guideline_el.attributes.get("identifier").cloned();
+        ret.guidelines.push(guideline);
+    }
+
+    if let Some(note_el) = glif.take_child("note") {
+        ret.note = note_el.get_text().map(|t| t.into_owned());
+    }
+
+    let mut goutline: GlifOutline = GlifOutline::new();
+
+    if let Some(mut outline_el) = glif.take_child("outline") {
+        while let Some(mut contour_el) = outline_el.take_child("contour") {
+            let mut gcontour = GlifContour::new();
+            while let Some(point_el) = contour_el.take_child("point") {
+                let mut gpoint = GlifPoint::new();
+                gpoint.x = point_el
+                    .attributes
+                    .get("x")
+                    .ok_or(input_error!("<point> missing x"))?
+                    .parse()
+                    .or(Err(input_error!("<point> x not float")))?;
+                gpoint.y = point_el
+                    .attributes
+                    .get("y")
+                    .ok_or(input_error!("<point> missing y"))?
+                    .parse()
+                    .or(Err(input_error!("<point> y not float")))?;
+                gpoint.name = point_el.attributes.get("name").cloned();
+                gpoint.ptype = point_el.attributes.get("type").map(|s| s.as_str()).unwrap_or("offcurve").into();
+                if point_el.attributes.get("smooth").map(|s| s == "yes").unwrap_or(false) && gpoint.ptype != PointType::OffCurve {
+                    gpoint.smooth = true;
+                }
+                if gpoint.ptype.is_valid() {
+                    gcontour.push(gpoint);
+                } else {
+                    Err(GlifInputError(format!("Shouldn't write <point type={}> to UFO .glif!", gpoint.ptype)))?;
+                }
+            }
+            if !gcontour.is_empty() {
+                goutline.push(gcontour);
+            }
+        }
+
+        while let Some(component_el) = outline_el.take_child("component") {
+            let mut gcomponent = GlifComponent::new();
+            load_matrix_and_identifier!(component_el, gcomponent, (xScale, xyScale, yxScale, yScale, xOffset, yOffset));
+            gcomponent.base = component_el.attributes.get("base").ok_or(input_error!("<component> missing base"))?.clone();
+            ret.components.vec.push(gcomponent);
+        }
+    }
+
+    #[cfg(feature = "glifserde")]
+    if let Some(mut lib) = glif.take_child("lib") {
+        let mut plist_temp: Vec<u8> = vec![];
+        lib.name = String::from("plist");
+        match lib.write(&mut plist_temp).map(|()|plist::from_bytes(&plist_temp)) {
+            Ok(Ok(lib_p)) => ret.lib = Lib::Plist(lib_p),
+            Err(_) => ret.lib = Lib::Xml(lib),
+            Ok(Err(_)) => {},
+        }
+    }
+
+    let outline: Outline<PD> = goutline.try_into()?;
+
+    if !outline.is_empty() || !ret.components.vec.is_empty() {
+        ret.outline = Some(outline);
+    }
+
+    Ok(ret)
+}
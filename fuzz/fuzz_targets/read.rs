@@ -0,0 +1,37 @@
+//! Feeds arbitrary bytes through every public read path, and whatever reads back through the
+//! writers and outline conversions. None of these may panic, however bad the input.
+#![no_main]
+
+use glifparser::glif::{self, WriteOptions};
+use glifparser::outline::{FromKurbo as _, IntoKurbo as _, IntoPenOperations as _, Reverse as _};
+use glifparser::pedantry::{Level, Mend};
+use glifparser::{Glif, Outline, Pedantry};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(xml) = std::str::from_utf8(data) else { return };
+
+    let _ = glif::scan(xml);
+    let _ = glif::read_with_diagnostics::<()>(xml, Pedantry::default());
+    let _ = glif::read_with_mends::<()>(xml, Pedantry::new(Level::TrueType, Mend::Always));
+    if let Ok(lossless) = glif::read_lossless::<()>(xml) {
+        let _ = glif::write_lossless(&lossless);
+    }
+
+    let Ok(glif) = glif::read::<()>(xml) else { return };
+    let _ = glif::write(&glif);
+    let _ = glif::write_format_1(&glif);
+    let _ = glif::write_with_options(&glif, &WriteOptions::normalized());
+    converted(glif);
+});
+
+fn converted(glif: Glif<()>) {
+    let Some(outline) = glif.outline else { return };
+    for mut contour in outline.clone() {
+        let _ = contour.into_pen_operations();
+        contour.reverse();
+    }
+    if let Ok(path) = outline.into_kurbo() {
+        let _: Outline<()> = Outline::from_kurbo(&path);
+    }
+}
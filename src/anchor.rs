@@ -8,8 +8,9 @@ use std::fmt::Debug;
 #[cfg(feature = "glifserde")]
 use serde::{Serialize, Deserialize};
 
+use crate::color::Color;
 use crate::error::GlifParserError;
-use crate::pedantry::{FloatClass, Mend, Pedantry};
+use crate::pedantry::{FloatClass, Pedantry};
 use crate::point::PointData;
 use crate::string::GlifStringLenOne;
 
@@ -43,7 +44,7 @@ pub trait FromOption<S: Into<AnchorType>>: Default {
 impl<S: Into<Self>> FromOption<S> for AnchorType {}
 
 #[cfg_attr(feature = "glifserde", derive(Serialize, Deserialize))]
-#[derive(Constructor, Clone, Debug, Default, PartialEq)]
+#[derive(Clone, Debug, Default, PartialEq)]
 pub struct Anchor<PD: PointData> {
     pub x: f32,
     pub y: f32,
@@ -53,26 +54,63 @@ pub struct Anchor<PD: PointData> {
     pub data: PD,
     #[cfg_attr(feature = "glifserde", serde(default))]
     pub atype: AnchorType,
+    #[cfg_attr(feature = "glifserde", serde(default))]
+    pub color: Option<Color>,
+    #[cfg_attr(feature = "glifserde", serde(default))]
+    pub identifier: Option<String>,
+    /// The anchor's lib in `public.objectLibs`
+    #[cfg_attr(feature = "glifserde", serde(default))]
+    pub lib: plist::Dictionary,
 }
 
 impl<PD: PointData> Anchor<PD> {
+    pub fn new(x: f32, y: f32, class: Option<String>, data: PD, atype: AnchorType) -> Self {
+        Self { x, y, class, data, atype, color: None, identifier: None, lib: plist::Dictionary::new() }
+    }
+
+    pub fn color(mut self, color: impl Into<Color>) -> Self {
+        self.color = Some(color.into());
+        self
+    }
+
+    pub fn identifier(mut self, identifier: impl Into<String>) -> Self {
+        self.identifier = Some(identifier.into());
+        self
+    }
+
+    /// `ga`, rounded if `pedantry` requires anchors to be integers. The .glif reader rounds
+    /// anchors itself, reporting each as a mend, and so converts with [`From`] instead.
     pub fn from_glif(ga: &GlifAnchor, pedantry: Pedantry) -> Result<Self, GlifParserError> {
-        let (x, y) = (pedantry.level.maybe_round(ga.x, FloatClass::Anchor), pedantry.level.maybe_round(ga.y, FloatClass::Anchor));
-        let class = ga.class.as_ref().map(|gs|gs.to_string());
-        if pedantry.mend != Mend::Always && (!ga.x.holding_integer().is_ok() || !ga.y.holding_integer().is_ok()) {
+        if !pedantry.allows(ga.x, FloatClass::Anchor) || !pedantry.allows(ga.y, FloatClass::Anchor) {
             return Err(GlifParserError::PedanticXmlParseError("Anchor was a float, not an integer!".to_string()))
         }
+        let (x, y) = (pedantry.level.maybe_round(ga.x, FloatClass::Anchor), pedantry.level.maybe_round(ga.y, FloatClass::Anchor));
+        Ok(Self { x, y, ..Self::from(ga) })
+    }
+}
+
+impl<PD: PointData> From<&GlifAnchor> for Anchor<PD> {
+    fn from(ga: &GlifAnchor) -> Self {
+        let class = ga.class.as_ref().map(|gs|gs.to_string());
         let atype = AnchorType::from_option(class.as_ref());
-        Ok(Self {
-            x, y, class, atype, data: PD::default()
-        })
+        Self {
+            x: ga.x.into(), y: ga.y.into(), class, atype, data: PD::default(), color: ga.color, identifier: ga.identifier.clone(), lib: plist::Dictionary::new()
+        }
     }
 }
 
 #[cfg_attr(feature = "glifserde", derive(Serialize, Deserialize))]
-#[derive(Constructor, Clone, Debug, Default, PartialEq)]
+#[derive(Clone, Debug, Default, PartialEq)]
 pub struct GlifAnchor {
     pub x: IntegerOrFloat,
     pub y: IntegerOrFloat,
     pub class: Option<GlifStringLenOne>,
+    pub color: Option<Color>,
+    pub identifier: Option<String>,
+}
+
+impl GlifAnchor {
+    pub fn new(x: IntegerOrFloat, y: IntegerOrFloat, class: Option<GlifStringLenOne>) -> Self {
+        Self { x, y, class, ..Default::default() }
+    }
 }
@@ -11,6 +11,12 @@ impl<PD: PointData> IntoXML for Anchor<PD> {
         if let Some(class) = &self.class {
             anchor_node.attributes.insert("name".to_owned(), class.to_string());
         }
+        if let Some(c) = self.color {
+            anchor_node.attributes.insert("color".to_owned(), c.to_string());
+        }
+        if let Some(i) = &self.identifier {
+            anchor_node.attributes.insert("identifier".to_owned(), i.clone());
+        }
         anchor_node
     }
 }
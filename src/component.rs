@@ -1,13 +1,18 @@
 //! .glif `<component>`
 
+mod cache;
+pub use cache::FlatteningCache;
+mod provider;
+pub use provider::{ComponentProvider, FilesystemProvider};
 mod xml;
 
+use crate::anchor::Anchor;
 use crate::error::GlifParserError;
 #[cfg(feature = "mfek")]
 use crate::glif::mfek::MFEKGlif;
 use crate::glif::{self, Glif};
 use crate::matrix::{ApplyMatrix as _, GlifMatrix};
-use crate::outline::Outline;
+use crate::outline::{Outline, Reverse as _};
 use crate::point::PointData;
 
 use integer_or_float::IntegerOrFloat;
@@ -19,11 +24,12 @@ use IntegerOrFloat::Float;
 use serde::{Deserialize, Serialize};
 
 use std::collections::HashSet;
+use std::hash::{Hash, Hasher};
 use std::path::{Path, PathBuf};
 
 #[allow(non_snake_case)] // to match UFO spec https://unifiedfontobject.org/versions/ufo3/glyphs/glif/#component
 #[cfg_attr(feature = "glifserde", derive(Serialize, Deserialize))]
-#[derive(Clone, Debug, Default, Hash, PartialEq)]
+#[derive(Clone, Debug, Default, PartialEq)]
 pub struct GlifComponent {
     pub base: String,
     pub filename: Option<PathBuf>,
@@ -34,6 +40,17 @@ pub struct GlifComponent {
     pub xOffset: IntegerOrFloat,
     pub yOffset: IntegerOrFloat,
     pub identifier: Option<String>,
+    /// The component's lib in `public.objectLibs`
+    #[cfg_attr(feature = "glifserde", serde(default))]
+    pub lib: plist::Dictionary,
+}
+
+/// Hashes all but the lib, as plist values can't be hashed.
+impl Hash for GlifComponent {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        (&self.base, &self.filename, &self.identifier).hash(state);
+        (self.xScale, self.xyScale, self.yxScale, self.yScale, self.xOffset, self.yOffset).hash(state);
+    }
 }
 
 #[cfg_attr(feature = "glifserde", derive(Serialize, Deserialize))]
@@ -112,7 +129,6 @@ impl ComponentRect {
     }
 }
 
-use std::fs;
 impl GlifComponent {
     /// Sets the filename of a component relative to its base's filename (`gliffn`)
     pub fn set_file_name<F: AsRef<Path>>(&mut self, gliffn: F) {
@@ -123,22 +139,17 @@ impl GlifComponent {
 
     /// Must have filename set, and that file must be readable, for this to work.
     pub fn to_component<PD: PointData>(&self) -> Result<Component<PD>, GlifParserError> {
-        let gliffn = &self
-            .filename
-            .as_ref()
-            .ok_or(GlifParserError::GlifFilenameNotSet(self.base.clone()))?;
+        self.to_component_with(&FilesystemProvider)
+    }
+
+    /// Get the base glyph from `provider`.
+    pub fn to_component_with<PD: PointData, P: ComponentProvider<PD> + ?Sized>(&self, provider: &P) -> Result<Component<PD>, GlifParserError> {
+        let newglif = provider.base_glif(self)?;
 
         let mut ret = Component::new();
         ret.matrix = self.matrix().into();
         ret.glif.name = self.base.clone();
-        ret.glif.filename = self.filename.clone();
-        let component_xml = fs::read_to_string(&gliffn).or(Err(GlifParserError::GlifFilenameNotSet(
-            "Glif filename leads to unreadable file".to_string(),
-        )))?;
-        let mut newglif: Glif<PD> = glif::read(&component_xml)?;
-        for component in newglif.components.vec.iter_mut() {
-            component.set_file_name(&gliffn);
-        }
+        ret.glif.filename = newglif.filename.or_else(|| self.filename.clone());
         ret.glif.components = newglif.components;
         ret.glif.anchors = newglif.anchors;
         ret.glif.outline = newglif.outline;
@@ -150,18 +161,96 @@ impl GlifComponent {
     }
 }
 
+/// Options for [`FlattenedGlif`] and [`FlatteningCache`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct FlattenOptions {
+    /// Bring the anchors of components' base glyphs, transformed, into the flattened glyph. When
+    /// two anchors have the same name, the first wins, in this order: the glyph's own anchors, then
+    /// each component's, in order, where a component's base glyph's own anchors come before those
+    /// of its components. Unnamed anchors are all kept. Carried anchors lose their identifiers, as
+    /// they'd no longer be unique.
+    pub anchors: bool,
+}
+
+/// Add `anchor`, transformed by `matrix`, to `anchors` unless one of its name is already there.
+fn carry_anchor<PD: PointData>(anchors: &mut Vec<Anchor<PD>>, mut anchor: Anchor<PD>, matrix: Affine) {
+    if anchor.class.is_some() && anchors.iter().any(|a| a.class == anchor.class) {
+        return
+    }
+    anchor.apply_matrix(matrix);
+    anchor.identifier = None;
+    anchors.push(anchor);
+}
+
+/// Clear the identifiers of the contours and points of `outline`, carried from a component, as
+/// they'd no longer be unique.
+fn clear_identifiers<PD: PointData>(outline: &mut Outline<PD>) {
+    for contour in outline.iter_mut() {
+        contour.identifier = None;
+        for point in contour.iter_mut() {
+            point.identifier = None;
+            point.a_attributes.identifier = None;
+            point.b_attributes.identifier = None;
+        }
+    }
+}
+
+/// Carry the anchors of the component tree `node`, under `matrix`, into `anchors`.
+fn carry_component_anchors<PD: PointData>(anchors: &mut Vec<Anchor<PD>>, node: &Node<Component<PD>>, matrix: Affine) {
+    let matrix = matrix * node.data().matrix;
+    for anchor in node.data().glif.anchors.iter() {
+        carry_anchor(anchors, anchor.clone(), matrix);
+    }
+    for child in node.iter() {
+        carry_component_anchors(anchors, child, matrix);
+    }
+}
+
 pub trait FlattenedGlif
 where
     Self: Clone,
 {
+    type Data: PointData;
+
     /// Check that all components in your .glif file really resolve, and if they do, get their
     /// contours and apply their matrices. If you want bounding rectangles as this process is done
     /// with a logical name for each rectangle you can draw, pass in `rects` as a `&mut`
-    fn flattened(&self, rects: &mut Option<Vec<ComponentRect>>) -> Result<Self, GlifParserError>;
+    ///
+    /// Contours and points taken from components lose their identifiers, as they'd no longer be
+    /// unique. Components are read from their files; see [`FlattenedGlif::flattened_with`].
+    fn flattened(&self, rects: &mut Option<Vec<ComponentRect>>) -> Result<Self, GlifParserError> {
+        self.flattened_with(rects, &FilesystemProvider)
+    }
+
+    /// As [`FlattenedGlif::flattened`], getting components' base glyphs from `provider`.
+    fn flattened_with<P: ComponentProvider<Self::Data> + ?Sized>(&self, rects: &mut Option<Vec<ComponentRect>>, provider: &P) -> Result<Self, GlifParserError> {
+        self.flattened_with_options(rects, provider, FlattenOptions::default())
+    }
+
+    /// As [`FlattenedGlif::flattened_with`], with `options`.
+    fn flattened_with_options<P: ComponentProvider<Self::Data> + ?Sized>(&self, rects: &mut Option<Vec<ComponentRect>>, provider: &P, options: FlattenOptions) -> Result<Self, GlifParserError>;
+}
+
+/// Apply the component tree `node`: its base glyph's contours, then those of its components, in
+/// order. `first` is whether no point has been met yet.
+fn apply_component_tree<PD: PointData>(
+    node: &Node<Component<PD>>,
+    first: &mut bool,
+    minx: &mut f32,
+    miny: &mut f32,
+    maxx: &mut f32,
+    maxy: &mut f32,
+    final_outline: &mut Outline<PD>,
+) {
+    apply_component_rect(node, first, minx, miny, maxx, maxy, final_outline);
+    for child in node.iter() {
+        apply_component_tree(child, first, minx, miny, maxx, maxy, final_outline);
+    }
 }
 
 fn apply_component_rect<PD: PointData>(
     last: &Node<Component<PD>>,
+    first: &mut bool,
     minx: &mut f32,
     miny: &mut f32,
     maxx: &mut f32,
@@ -178,12 +267,15 @@ fn apply_component_rect<PD: PointData>(
         pt = parent.parent();
     }
 
+    // A mirroring matrix reverses contours' direction, so reverse them back
+    let mirrored = matrices.iter().map(|m| m.determinant()).product::<f64>() < 0.;
+
     match (*last).data().glif.outline {
         Some(ref o) => {
             let mut to_transform = o.clone();
             for i in 0..to_transform.len() {
                 for j in 0..to_transform[i].len() {
-                    let is_first = i == 0 && j == 0;
+                    let is_first = std::mem::replace(first, false);
                     let mut p = to_transform[i][j].clone();
                     if p.x < *minx || is_first {
                         *minx = p.x;
@@ -205,6 +297,10 @@ fn apply_component_rect<PD: PointData>(
                     to_transform[i][j] = p;
                 }
             }
+            if mirrored {
+                to_transform = to_transform.to_reversed();
+            }
+            clear_identifiers(&mut to_transform);
             final_outline.extend(to_transform);
         }
         None => {}
@@ -213,33 +309,36 @@ fn apply_component_rect<PD: PointData>(
 
 #[rustfmt::skip]
 macro_rules! impl_flattened_glif {
-    ($glifstruct:ident, $outline:ident) => { 
+    ($glifstruct:ident, $outline:ident, $own_outline:expr) => { 
 
         impl<PD: PointData> FlattenedGlif for $glifstruct<PD> {
+            type Data = PD;
+
             /// Flatten a UFO .glif with components.
             ///
             /// Can fail if the .glif's components form an infinite loop.
-            // How this works is we start at the bottom of the tree, take all of the Affine matrices which
-            // describe the transformation of the glyph's points, and continuously apply them until we run
-            // out of nodes of the tree. Finally, we set our outline to be the final transformed outline,
-            // and consider ourselves as no longer being made up of components.
-            fn flattened(&self, rects: &mut Option<Vec<ComponentRect>>) -> Result<Self, GlifParserError> {
+            // How this works is we walk each component's tree, base glyph first, then its
+            // components in order, taking all of the Affine matrices which describe the
+            // transformation of each node's points and applying them. Finally, we set our outline
+            // to be the final transformed outline, and consider ourselves as no longer being made
+            // up of components.
+            fn flattened_with_options<P: ComponentProvider<PD> + ?Sized>(&self, rects: &mut Option<Vec<ComponentRect>>, provider: &P, options: FlattenOptions) -> Result<Self, GlifParserError> {
                 let mut ret = self.clone();
-                let components_r: Result<Forest<Component<PD>>, _> = (ret.components).into();
-                let components = components_r?;
-                let mut final_outline: Outline<PD> = Outline::new();
+                let components = ret.components.clone().to_forest(provider)?;
+
+                if options.anchors {
+                    for component in components.iter() {
+                        carry_component_anchors(&mut ret.anchors, component, Affine::IDENTITY);
+                    }
+                }
+
+                let mut final_outline: Outline<PD> = $own_outline(self);
                 let mut component_rects = vec![];
 
-                for mut component in components {
+                for component in components.iter() {
                     let (mut minx, mut miny, mut maxx, mut maxy) = (0., 0., 0., 0.);
-                    // This unwrap is safe because at this point we know we haven't yet exhausted the
-                    // Forest<Component>.
                     let component_name = component.data().glif.name.clone();
-                    apply_component_rect(&component, &mut minx, &mut miny, &mut maxx, &mut maxy, &mut final_outline);
-                    while let Some(last) = component.back() {
-                        apply_component_rect(&last, &mut minx, &mut miny, &mut maxx, &mut maxy, &mut final_outline);
-                        component.pop_back();
-                    }
+                    apply_component_tree(component, &mut true, &mut minx, &mut miny, &mut maxx, &mut maxy, &mut final_outline);
                     component_rects.push(ComponentRect { minx, maxx, miny, maxy, name: component_name });
                 }
 
@@ -258,9 +357,11 @@ macro_rules! impl_flattened_glif {
 
     }
 }
-impl_flattened_glif!(Glif, outline);
+// A Glif keeps its own contours, as FlatteningCache does. An MFEKGlif's own contours are in its
+// layers, so its flattened outline is only its components'.
+impl_flattened_glif!(Glif, outline, |glif: &Glif<PD>| glif.outline.clone().unwrap_or_default());
 #[cfg(feature = "mfek")]
-impl_flattened_glif!(MFEKGlif, flattened);
+impl_flattened_glif!(MFEKGlif, flattened, |_: &MFEKGlif<PD>| Outline::new());
 
 /// # Conversion
 ///
@@ -282,12 +383,19 @@ impl_flattened_glif!(MFEKGlif, flattened);
 /// [`Forest`]. Sometimes, .glif files can be malformed, containing components which refer to
 /// themselves or to components higher up the tree.
 impl<PD: PointData> From<GlifComponents> for Result<Forest<Component<PD>>, GlifParserError> {
-    fn from(mut glifcs: GlifComponents) -> Self {
+    fn from(glifcs: GlifComponents) -> Self {
+        glifcs.to_forest(&FilesystemProvider)
+    }
+}
+
+impl GlifComponents {
+    /// As [§ Conversion](#conversion), getting components' base glyphs from `provider`.
+    pub fn to_forest<PD: PointData, P: ComponentProvider<PD> + ?Sized>(mut self, provider: &P) -> Result<Forest<Component<PD>>, GlifParserError> {
         let mut forest = Forest::new();
-        let components: Vec<_> = glifcs.vec.drain(..).collect();
+        let components: Vec<_> = self.vec.drain(..).collect();
 
-        let cs  = components.into_iter().map(|c| {
-            glifcs.build_component_tree(c.to_component()?)
+        let cs = components.into_iter().map(|c| {
+            self.build_component_tree_with(c.to_component_with(provider)?, provider)
         }).collect::<Result<Vec<_>, GlifParserError>>()?;
 
         for c in cs {
@@ -311,6 +419,16 @@ impl GlifComponents {
     pub fn build_component_tree<PD: PointData>(
         &mut self,
         component: Component<PD>,
+    ) -> Result<Tree<Component<PD>>, GlifParserError> {
+        self.build_component_tree_with(component, &FilesystemProvider)
+    }
+
+    /// As [`GlifComponents::build_component_tree`], getting components' base glyphs from
+    /// `provider`.
+    pub fn build_component_tree_with<PD: PointData, P: ComponentProvider<PD> + ?Sized>(
+        &mut self,
+        component: Component<PD>,
+        provider: &P,
     ) -> Result<Tree<Component<PD>>, GlifParserError> {
         let mut tree = Tree::new(component.clone());
 
@@ -322,7 +440,7 @@ impl GlifComponents {
                 )));
             }
 
-            tree.push_back(self.build_component_tree(gc.to_component()?)?);
+            tree.push_back(self.build_component_tree_with(gc.to_component_with(provider)?, provider)?);
             self.uniques.remove(&gc.base);
         }
 
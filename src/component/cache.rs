@@ -0,0 +1,147 @@
+//! [`FlatteningCache`], for flattening many glyphs which share components, e.g. a whole font.
+
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+
+use super::{carry_anchor, clear_identifiers, ComponentProvider, FlattenOptions, GlifComponent, GlifComponents};
+use crate::anchor::Anchor;
+use crate::error::GlifParserError;
+use crate::glif::Glif;
+use crate::matrix::ApplyMatrix as _;
+use crate::outline::{Outline, Reverse as _};
+use crate::point::PointData;
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+/// A glyph's outline and anchors with its components brought in.
+#[derive(Clone, Debug)]
+struct Flattened<PD: PointData> {
+    outline: Outline<PD>,
+    anchors: Vec<Anchor<PD>>,
+}
+
+type CachedFlattened<PD> = Result<Arc<Flattened<PD>>, GlifParserError>;
+
+/// Flattens glyphs, keeping the flattened outline of every base glyph it meets so that each is
+/// only got from the [`ComponentProvider`], and flattened, once. Errors are kept too, so a
+/// component cycle is found once, however many glyphs use it.
+///
+/// As with [`FlattenedGlif::flattened`](super::FlattenedGlif::flattened), the glyph's own contours
+/// are kept, followed by its components', in order.
+///
+/// Contours of mirrored components are reversed, keeping their direction, and with
+/// [`FlattenOptions::anchors`] components' anchors are carried as by
+/// [`FlattenedGlif`](super::FlattenedGlif).
+///
+/// The cache doesn't notice changes to the provider's glyphs; call [`FlatteningCache::clear`]
+/// after making any.
+#[derive(Debug)]
+pub struct FlatteningCache<PD: PointData, P: ComponentProvider<PD>> {
+    provider: P,
+    options: FlattenOptions,
+    bases: RwLock<HashMap<String, CachedFlattened<PD>>>,
+}
+
+impl<PD: PointData, P: ComponentProvider<PD>> FlatteningCache<PD, P> {
+    pub fn new(provider: P) -> Self {
+        Self::with_options(provider, FlattenOptions::default())
+    }
+
+    pub fn with_options(provider: P, options: FlattenOptions) -> Self {
+        Self { provider, options, bases: RwLock::new(HashMap::new()) }
+    }
+
+    pub fn provider(&self) -> &P {
+        &self.provider
+    }
+
+    /// Forget every base glyph flattened so far.
+    pub fn clear(&mut self) {
+        self.bases.get_mut().unwrap_or_else(|e| e.into_inner()).clear();
+    }
+
+    /// `glif`'s outline with its components decomposed.
+    pub fn flattened_outline(&self, glif: &Glif<PD>) -> Result<Outline<PD>, GlifParserError> {
+        Ok(self.flatten(glif)?.outline)
+    }
+
+    /// `glif` with its components decomposed into its outline, and removed.
+    pub fn flattened(&self, glif: &Glif<PD>) -> Result<Glif<PD>, GlifParserError> {
+        let flattened = self.flatten(glif)?;
+        let mut ret = glif.clone();
+        ret.outline = Some(flattened.outline);
+        if self.options.anchors {
+            ret.anchors = flattened.anchors;
+        }
+        ret.components = GlifComponents::new();
+        Ok(ret)
+    }
+
+    /// Flatten each of `glyphs`.
+    pub fn flattened_all<'a, I>(&self, glyphs: I) -> Vec<Result<Glif<PD>, GlifParserError>>
+    where
+        I: IntoIterator<Item = &'a Glif<PD>>,
+        PD: 'a,
+    {
+        glyphs.into_iter().map(|glif| self.flattened(glif)).collect()
+    }
+
+    /// As [`FlatteningCache::flattened_all`], on all cores, sharing the cache between them.
+    #[cfg(feature = "parallel")]
+    pub fn flattened_all_parallel(&self, glyphs: &[Glif<PD>]) -> Vec<Result<Glif<PD>, GlifParserError>>
+    where
+        PD: Send + Sync,
+        P: Sync,
+    {
+        glyphs.par_iter().map(|glif| self.flattened(glif)).collect()
+    }
+
+    fn flatten(&self, glif: &Glif<PD>) -> Result<Flattened<PD>, GlifParserError> {
+        let mut stack = vec![glif.name.clone()];
+        let flattened = self.flatten_inner(glif, &mut stack)?;
+        Ok(Arc::try_unwrap(flattened).unwrap_or_else(|flattened| (*flattened).clone()))
+    }
+
+    /// `glif`'s own contours and anchors, then those of its components. `stack` holds the names of
+    /// the glyphs being flattened, outermost first.
+    fn flatten_inner(&self, glif: &Glif<PD>, stack: &mut Vec<String>) -> CachedFlattened<PD> {
+        let mut outline = glif.outline.clone().unwrap_or_default();
+        let mut anchors = glif.anchors.clone();
+        for component in glif.components.vec.iter() {
+            let base = self.base(component, stack)?;
+            let matrix = component.matrix().into();
+            let mut base_outline = base.outline.clone();
+            base_outline.apply_matrix(matrix);
+            // A mirroring matrix reverses contours' direction, so reverse them back
+            if matrix.determinant() < 0. {
+                base_outline = base_outline.to_reversed();
+            }
+            clear_identifiers(&mut base_outline);
+            outline.extend(base_outline);
+            for anchor in base.anchors.iter() {
+                carry_anchor(&mut anchors, anchor.clone(), matrix);
+            }
+        }
+        Ok(Arc::new(Flattened { outline, anchors }))
+    }
+
+    fn base(&self, component: &GlifComponent, stack: &mut Vec<String>) -> CachedFlattened<PD> {
+        if let Some(cached) = self.bases.read().unwrap_or_else(|e| e.into_inner()).get(&component.base) {
+            return cached.clone()
+        }
+        if stack.contains(&component.base) {
+            return Err(GlifParserError::GlifComponentsCyclical(format!(
+                "{} refers to {} (trying to flatten {})",
+                stack.join(" → "), component.base, stack[0]
+            )))
+        }
+
+        stack.push(component.base.clone());
+        let flattened = self.provider.base_glif(component).and_then(|glif| self.flatten_inner(&glif, stack));
+        stack.pop();
+
+        self.bases.write().unwrap_or_else(|e| e.into_inner()).insert(component.base.clone(), flattened.clone());
+        flattened
+    }
+}
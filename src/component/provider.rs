@@ -0,0 +1,50 @@
+//! Where the base glyphs of components come from: [`ComponentProvider`].
+
+use std::collections::{BTreeMap, HashMap};
+
+use super::GlifComponent;
+use crate::error::GlifParserError;
+use crate::glif::{self, Glif};
+use crate::point::PointData;
+
+/// Resolves components to their base glyphs, for building component trees and flattening.
+///
+/// Most providers map [`GlifComponent::base`] to a glyph, e.g. from memory or a database; the
+/// whole component is passed so that [`FilesystemProvider`] can use its `filename`.
+pub trait ComponentProvider<PD: PointData> {
+    fn base_glif(&self, component: &GlifComponent) -> Result<Glif<PD>, GlifParserError>;
+}
+
+/// Reads each component's base from [`GlifComponent::filename`], as set when its glyph was read
+/// from a file. This is the provider used by [`GlifComponent::to_component`] and
+/// [`FlattenedGlif::flattened`](super::FlattenedGlif::flattened).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct FilesystemProvider;
+
+impl<PD: PointData> ComponentProvider<PD> for FilesystemProvider {
+    fn base_glif(&self, component: &GlifComponent) -> Result<Glif<PD>, GlifParserError> {
+        let gliffn = component
+            .filename
+            .as_ref()
+            .ok_or(GlifParserError::GlifFilenameNotSet(component.base.clone()))?;
+        glif::read_from_filename(gliffn)
+    }
+}
+
+impl<PD: PointData> ComponentProvider<PD> for HashMap<String, Glif<PD>> {
+    fn base_glif(&self, component: &GlifComponent) -> Result<Glif<PD>, GlifParserError> {
+        self.get(&component.base).cloned().ok_or_else(|| GlifParserError::GlifComponentBaseMissing(component.base.clone()))
+    }
+}
+
+impl<PD: PointData> ComponentProvider<PD> for BTreeMap<String, Glif<PD>> {
+    fn base_glif(&self, component: &GlifComponent) -> Result<Glif<PD>, GlifParserError> {
+        self.get(&component.base).cloned().ok_or_else(|| GlifParserError::GlifComponentBaseMissing(component.base.clone()))
+    }
+}
+
+impl<PD: PointData, P: ComponentProvider<PD> + ?Sized> ComponentProvider<PD> for &P {
+    fn base_glif(&self, component: &GlifComponent) -> Result<Glif<PD>, GlifParserError> {
+        (**self).base_glif(component)
+    }
+}
@@ -3,6 +3,10 @@
 #[cfg(feature = "mfek")]
 pub mod mfek;
 
+mod diagnostic;
+pub use diagnostic::*;
+mod location;
+pub use location::*;
 mod string;
 pub use string::*;
 
@@ -11,8 +15,9 @@ use crate::point::PointType;
 use std::error::Error;
 use std::fmt::{Formatter, Display};
 use std::io;
-use std::rc::Rc;
+use std::sync::Arc;
 
+use quick_xml::events::attributes::AttrError;
 use xmltree::{ParseError, Error as XMLTreeError};
 #[cfg(feature = "glifserde")]
 use plist::Error as PlistError;
@@ -22,7 +27,7 @@ pub type GlifParserResult<T> = Result<T, GlifParserError>;
 #[derive(Debug, Clone)]
 pub enum GlifParserError {
     /// OS error when reading glif
-    GlifFileIoError(Option<Rc<io::Error>>),
+    GlifFileIoError(Option<Arc<io::Error>>),
     /// Self-built Outline/Contour error.
     GlifOutlineHasBadPointType{ci: usize, pi: usize, ptype: PointType},
     GlifContourHasBadPointType{pi: usize, ptype: PointType},
@@ -33,6 +38,10 @@ pub enum GlifParserError {
     GlifFilenameInsane(String),
     /// Components of the glyph form a loop
     GlifComponentsCyclical(String),
+    /// No glyph of this name in the glyph set's contents.plist
+    GlyphNotInGlyphSet(String),
+    /// A component's base glyph can't be found by the [`ComponentProvider`](crate::component::ComponentProvider)
+    GlifComponentBaseMissing(String),
     /// .glif has invalid <lib>
     GlifLibError,
 
@@ -54,7 +63,7 @@ pub enum GlifParserError {
     /// Image not decodable
     ImageNotDecodable,
     /// OS error when reading image
-    ImageIoError(Option<Rc<io::Error>>),
+    ImageIoError(Option<Arc<io::Error>>),
 
     /// Color (for guidelines, images, etc) not RGBA
     ColorNotRGBA,
@@ -70,11 +79,52 @@ pub enum GlifParserError {
     ContourNoPrevious(usize),
     /// No next on an open contour
     ContourNoNext(usize),
+
+    /// An error reading a .glif, with where in the file it arose. Errors from reading a .glif or a
+    /// glyph set may be wrapped in this, I/O errors included, so match on
+    /// [`without_location()`](GlifParserError::without_location) to find what went wrong, e.g.
+    /// `matches!(e.without_location(), GlifParserError::GlifFileIoError(_))`.
+    Located{error: Box<GlifParserError>, location: GlifErrorLocation},
 }
 
-impl Display for GlifParserError {
-    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), std::fmt::Error> { 
-        write!(f, "glifparser error: {}", match self {
+impl GlifParserError {
+    /// Where in the .glif this error arose, if known.
+    pub fn location(&self) -> Option<&GlifErrorLocation> {
+        match self {
+            Self::Located{location, ..} => Some(location),
+            _ => None,
+        }
+    }
+
+    /// This error, without its location.
+    pub fn without_location(&self) -> &GlifParserError {
+        match self {
+            Self::Located{error, ..} => error.without_location(),
+            e => e,
+        }
+    }
+
+    /// Attach `location`, unless a location is already known.
+    pub(crate) fn located(self, location: GlifErrorLocation) -> Self {
+        match self {
+            Self::Located{..} => self,
+            error => Self::Located{error: Box::new(error), location},
+        }
+    }
+
+    pub(crate) fn with_filename(self, filename: &std::path::Path) -> Self {
+        match self {
+            Self::Located{error, mut location} => {
+                location.filename = Some(filename.to_path_buf());
+                Self::Located{error, location}
+            },
+            error => error.located(GlifErrorLocation { filename: Some(filename.to_path_buf()), ..Default::default() }),
+        }
+    }
+
+    pub(crate) fn message(&self) -> String {
+        match self {
+
             Self::GlifFileIoError(ioe) => {
                 format!("System error when loading glif file: {:?}", ioe)
             },
@@ -96,6 +146,12 @@ impl Display for GlifParserError {
             Self::GlifComponentsCyclical(s) => {
                 format!("Glyph components are cyclical: {}", &s)
             },
+            Self::GlyphNotInGlyphSet(s) => {
+                format!("Glyph not in glyph set: {}", &s)
+            },
+            Self::GlifComponentBaseMissing(s) => {
+                format!("Component base glyph not found: {}", &s)
+            },
             Self::GlifLibError => {
                 format!("Glif <lib> invalid")
             },
@@ -148,17 +204,39 @@ impl Display for GlifParserError {
             Self::ContourNoNext(len) => {
                 format!("Asked for next index of last point, {}, on an open contour", len)
             }
-        })
+
+            Self::Located{error, location} => {
+                format!("{}: {}", location, error.message())
+            }
+        }
+    }
+}
+
+impl Display for GlifParserError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), std::fmt::Error> {
+        write!(f, "glifparser error: {}", self.message())
     }
 }
 
-// the parsing function in read_ufo_glif can only return this error type
 impl From<ParseError> for GlifParserError {
     fn from(e: ParseError) -> Self {
         Self::XmlParseError(format!("{}", e))
     }
 }
 
+// read_ufo_glif's streaming parser returns these
+impl From<quick_xml::Error> for GlifParserError {
+    fn from(e: quick_xml::Error) -> Self {
+        Self::XmlParseError(format!("{}", e))
+    }
+}
+
+impl From<AttrError> for GlifParserError {
+    fn from(e: AttrError) -> Self {
+        Self::XmlParseError(format!("{}", e))
+    }
+}
+
 // . . . therefore it's OK to consider this a write-time error type
 impl From<XMLTreeError> for GlifParserError {
     fn from(e: XMLTreeError) -> Self {
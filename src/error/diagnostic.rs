@@ -0,0 +1,54 @@
+//! Problems found by [`read_with_diagnostics`](crate::glif::read_with_diagnostics)
+
+use std::fmt::{Display, Formatter};
+
+use super::{GlifErrorLocation, GlifParserError};
+
+/// How serious a [`Diagnostic`] is
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, IsVariant)]
+pub enum Severity {
+    /// Input which breaks the spec or the requested [`Pedantry`](crate::Pedantry). Reading
+    /// normally would have failed here; the offending element was skipped.
+    Error,
+    /// Input which glifparser fixed or upgraded, as the [`Pedantry`](crate::Pedantry) allows.
+    Mend,
+    /// Input which is suspect, or which glifparser ignored, but which isn't an error.
+    Warning,
+}
+
+impl Display for Severity {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), std::fmt::Error> {
+        write!(f, "{}", match self {
+            Self::Error => "error",
+            Self::Mend => "mended",
+            Self::Warning => "warning",
+        })
+    }
+}
+
+/// A problem with a .glif, and where it is
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    pub message: String,
+    pub location: GlifErrorLocation,
+}
+
+impl Diagnostic {
+    pub(crate) fn from_error(error: GlifParserError) -> Self {
+        Self {
+            severity: Severity::Error,
+            message: error.without_location().message(),
+            location: error.location().cloned().unwrap_or_default(),
+        }
+    }
+}
+
+impl Display for Diagnostic {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), std::fmt::Error> {
+        match self.location.to_string().as_str() {
+            "" => write!(f, "{}: {}", self.severity, self.message),
+            location => write!(f, "{}: {}: {}", self.severity, location, self.message),
+        }
+    }
+}
@@ -0,0 +1,104 @@
+//! Where in a .glif file an error arose
+
+use std::fmt::{Display, Formatter};
+use std::path::PathBuf;
+
+/// Where in a .glif an error arose. Any part may be unknown, e.g. there's no filename when reading
+/// from a string.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GlifErrorLocation {
+    /// Set by [`read_from_filename`](crate::glif::read_from_filename), or when a filename is given
+    /// to e.g. [`read_from_reader`](crate::glif::read_from_reader).
+    pub filename: Option<PathBuf>,
+    /// 1-based line of the start of the element (or, for XML syntax errors, of the error).
+    pub line: Option<u64>,
+    /// 1-based column, as `line`.
+    pub column: Option<u64>,
+    /// Elements leading from `<glyph>` to the erroneous element, each with its index among its
+    /// siblings of the same name. So, `[("outline", 0), ("contour", 3), ("point", 12)]` is the 13th
+    /// point of the 4th contour.
+    pub path: Vec<(String, usize)>,
+}
+
+impl GlifErrorLocation {
+    /// Locate the element at `path`, which starts at `line` and `column`.
+    pub(crate) fn new((line, column): (u64, u64), path: &[(&'static str, usize)]) -> Self {
+        Self {
+            path: path.iter().map(|(name, idx)| (name.to_string(), *idx)).collect(),
+            line: Some(line),
+            column: Some(column),
+            ..Default::default()
+        }
+    }
+}
+
+/// Finds the 1-based line and column (in characters) of byte offsets into a .glif. A reader looks
+/// up offsets in mostly increasing order, so the cursor moves from the last one rather than
+/// rescanning from the start, and locating every element of a file takes linear time.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct LineColumnCursor {
+    offset: usize,
+    line: u64,
+    column: u64,
+}
+
+impl Default for LineColumnCursor {
+    fn default() -> Self {
+        Self { offset: 0, line: 1, column: 1 }
+    }
+}
+
+/// Characters in `bytes`, counted as the bytes which aren't UTF-8 continuation bytes.
+fn chars(bytes: &[u8]) -> u64 {
+    bytes.iter().filter(|b| **b & 0xC0 != 0x80).count() as u64
+}
+
+impl LineColumnCursor {
+    pub(crate) fn seek(&mut self, xml: &str, offset: usize) -> (u64, u64) {
+        let xml = xml.as_bytes();
+        let offset = offset.min(xml.len());
+        if offset >= self.offset {
+            let passed = &xml[self.offset..offset];
+            match passed.iter().rposition(|b| *b == b'\n') {
+                Some(last_newline) => {
+                    self.line += passed.iter().filter(|b| **b == b'\n').count() as u64;
+                    self.column = chars(&passed[last_newline + 1..]) + 1;
+                },
+                None => self.column += chars(passed),
+            }
+        } else {
+            let passed = &xml[offset..self.offset];
+            match passed.iter().filter(|b| **b == b'\n').count() as u64 {
+                0 => self.column -= chars(passed),
+                newlines => {
+                    self.line -= newlines;
+                    let line_start = xml[..offset].iter().rposition(|b| *b == b'\n').map(|i| i + 1).unwrap_or(0);
+                    self.column = chars(&xml[line_start..offset]) + 1;
+                },
+            }
+        }
+        self.offset = offset;
+        (self.line, self.column)
+    }
+}
+
+impl Display for GlifErrorLocation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), std::fmt::Error> {
+        let mut parts = vec![];
+        if let Some(filename) = &self.filename {
+            parts.push(filename.display().to_string());
+        }
+        if let Some(line) = self.line {
+            parts.push(line.to_string());
+            if let Some(column) = self.column {
+                parts.push(column.to_string());
+            }
+        }
+        let path: Vec<String> = self.path.iter().map(|(name, idx)| format!("<{}> {}", name, idx)).collect();
+        match (parts.is_empty(), path.is_empty()) {
+            (_, true) => write!(f, "{}", parts.join(":")),
+            (true, false) => write!(f, "{}", path.join(", ")),
+            (false, false) => write!(f, "{} ({})", parts.join(":"), path.join(", ")),
+        }
+    }
+}
@@ -11,17 +11,38 @@ use crate::image::GlifImage;
 use crate::point::PointData;
 use crate::outline::Outline;
 
+mod bounds;
 mod conv;
+mod filename;
+pub use filename::{name_to_filename, user_name_to_file_name};
 mod lib;
 pub use lib::Lib;
+mod lossless;
+mod object_data;
+pub use lossless::LosslessGlif;
+pub use lossless::read_ufo_glif_lossless as read_lossless;
+pub use lossless::read_ufo_glif_lossless_pedantic as read_lossless_pedantic;
+pub use lossless::write_ufo_glif_lossless as write_lossless;
 mod read;
 pub use self::read::read_ufo_glif as read;
 pub use self::read::read_ufo_glif_pedantic as read_pedantic;
 pub use self::read::read_ufo_glif_from_filename as read_from_filename;
 pub use self::read::read_ufo_glif_from_filename_pedantic as read_from_filename_pedantic;
+pub use self::read::read_ufo_glif_with_diagnostics as read_with_diagnostics;
+pub use self::read::read_ufo_glif_with_mends as read_with_mends;
+pub use self::read::read_ufo_glif_from_filename_with_diagnostics as read_from_filename_with_diagnostics;
+pub use self::read::read_ufo_glif_from_reader as read_from_reader;
+pub use self::read::read_ufo_glif_from_reader_pedantic as read_from_reader_pedantic;
+pub use self::read::GlifHeader;
+pub use self::read::scan_ufo_glif as scan;
+pub use self::read::scan_ufo_glif_from_filename as scan_from_filename;
 mod write;
 pub use self::write::write_ufo_glif as write;
 pub use self::write::write_ufo_glif_to_filename as write_to_filename;
+pub use self::write::write_ufo_glif_to_writer as write_to_writer;
+pub use self::write::write_ufo_glif_with_options as write_with_options;
+pub use self::write::WriteOptions;
+pub use self::write::write_ufo_glif_format_1 as write_format_1;
 #[cfg(feature = "mfek")]
 pub mod mfek;
 pub mod xml;
@@ -33,6 +54,9 @@ use serde::{Deserialize, Serialize};
 #[cfg(feature = "mfek")]
 pub use mfek::*;
 
+/// The highest GLIF format 2 `formatMinor` this version of glifparser understands.
+pub const MAX_FORMAT_MINOR: u32 = 0;
+
 /// A UFO .glif
 ///
 /// TODO: use different generic types on Anchor and Guideline, making this declaration
@@ -57,6 +81,8 @@ pub struct Glif<PD: PointData> {
     #[cfg(feature = "glifimage")]
     pub images: Vec<GlifImage>,
     pub width: Option<u64>,
+    /// Vertical advance, `<advance height>`.
+    pub height: Option<u64>,
     pub unicode: Vec<char>,
     pub name: String,
     /// This is an arbitrary glyph comment, exactly like the comment field in FontForge SFD.
@@ -66,6 +92,9 @@ pub struct Glif<PD: PointData> {
     pub filename: Option<path::PathBuf>,
     /// glif private library
     pub lib: Lib,
+    /// `<glyph formatMinor>` as read. This is informational only: when writing, glifparser always
+    /// uses the lowest minor version the glyph requires, see [`Glif::required_format_minor`].
+    pub format_minor: u32,
 }
 
 impl<PD: PointData> Glif<PD> {
@@ -73,10 +102,19 @@ impl<PD: PointData> Glif<PD> {
         Self::default()
     }
 
+    /// The lowest GLIF format 2 `formatMinor` which can represent this glyph. GLIF 2.0 is
+    /// currently the only minor version, so this is always 0; when minor versions are added to the
+    /// spec, the features they introduce should be checked for here.
+    pub fn required_format_minor(&self) -> u32 {
+        0
+    }
+
     pub fn name_to_filename(&self) -> String {
         name_to_filename(&self.name, true)
     }
 
+    /// Whether `filename` is one the UFO 3 file name algorithm could have given this glyph's name,
+    /// allowing for the counter it appends to avoid clashes.
     pub fn filename_is_sane(&self) -> Result<bool, GlifParserError> {
         match &self.filename {
             Some(gfn) => {
@@ -85,7 +123,8 @@ impl<PD: PointData> Glif<PD> {
                     None => { return Err(GlifParserError::GlifFilenameInsane("Glif file name is directory".to_string())) }
                 };
 
-                Ok(self.name_to_filename() == gfn_fn.to_str().ok_or(GlifParserError::GlifFilenameInsane("Glif file name has unknown encoding".to_string()))?)
+                let gfn_fn = gfn_fn.to_str().ok_or(GlifParserError::GlifFilenameInsane("Glif file name has unknown encoding".to_string()))?;
+                Ok(filename::is_file_name_of(gfn_fn, &self.name, "", ".glif"))
             }
             None => Err(GlifParserError::GlifFilenameInsane("Glif file name is not set".to_string()))
         }
@@ -106,19 +145,3 @@ impl<PD: PointData> GlifLike for Glif<PD> {
         &self.filename
     }
 }
-
-#[inline]
-pub fn name_to_filename(name: &str, append_extension: bool) -> String {
-    let mut ret = String::new();
-    let chars: Vec<char> = name.chars().collect();
-    for c in chars {
-        ret.push(c);
-        if ('A'..'Z').contains(&c) {
-            ret.push('_');
-        }
-    }
-    if append_extension {
-        ret.push_str(".glif");
-    }
-    ret
-}
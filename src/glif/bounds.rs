@@ -0,0 +1,35 @@
+//! Bounding boxes of glyphs, components included.
+
+use kurbo::Rect;
+
+use super::Glif;
+use crate::component::{ComponentProvider, FilesystemProvider, FlatteningCache};
+use crate::error::GlifParserError;
+use crate::outline::Bounds as _;
+use crate::point::PointData;
+
+/// To find the bounds of many glyphs which share components, flatten them with one
+/// [`FlatteningCache`] and take the [`Bounds`](crate::outline::Bounds) of their outlines.
+impl<PD: PointData> Glif<PD> {
+    /// The exact bounds of the glyph's outline and its components', which are read from their
+    /// files. `None` if there are no points.
+    pub fn bounds(&self) -> Result<Option<Rect>, GlifParserError> {
+        self.bounds_with(&FilesystemProvider)
+    }
+
+    /// As [`Glif::bounds`], getting components' base glyphs from `provider`.
+    pub fn bounds_with<P: ComponentProvider<PD> + ?Sized>(&self, provider: &P) -> Result<Option<Rect>, GlifParserError> {
+        Ok(FlatteningCache::new(provider).flattened_outline(self)?.bounds())
+    }
+
+    /// The control box of the glyph's outline and its components', which are read from their
+    /// files.
+    pub fn control_bounds(&self) -> Result<Option<Rect>, GlifParserError> {
+        self.control_bounds_with(&FilesystemProvider)
+    }
+
+    /// As [`Glif::control_bounds`], getting components' base glyphs from `provider`.
+    pub fn control_bounds_with<P: ComponentProvider<PD> + ?Sized>(&self, provider: &P) -> Result<Option<Rect>, GlifParserError> {
+        Ok(FlatteningCache::new(provider).flattened_outline(self)?.control_bounds())
+    }
+}
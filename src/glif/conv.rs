@@ -1,22 +1,33 @@
+use crate::error::GlifParserError;
 use crate::glif::Glif;
 use crate::glif::read::read_ufo_glif;
 use crate::glif::write::{write_ufo_glif, write_ufo_glif_data};
 use crate::point::PointData;
 
-impl<PD: PointData, S: AsRef<str>> From<S> for Glif<PD> {
-    fn from(s: S) -> Self {
-        read_ufo_glif(s.as_ref()).expect("Called Into<&str/String> on invalid .glif data!")
+impl<PD: PointData> TryFrom<&str> for Glif<PD> {
+    type Error = GlifParserError;
+    fn try_from(s: &str) -> Result<Self, GlifParserError> {
+        read_ufo_glif(s)
     }
 }
 
-impl<PD: PointData> From<&Glif<PD>> for Vec<u8> {
-    fn from(g: &Glif<PD>) -> Self {
-        write_ufo_glif_data(g).expect("Called Into<Vec<u8>> on invalid in-memory Glif!")
+impl<PD: PointData> TryFrom<String> for Glif<PD> {
+    type Error = GlifParserError;
+    fn try_from(s: String) -> Result<Self, GlifParserError> {
+        read_ufo_glif(&s)
     }
 }
 
-impl<PD: PointData> From<&Glif<PD>> for String {
-    fn from(g: &Glif<PD>) -> Self {
-        write_ufo_glif(g).expect("Called Into<String> on invalid in-memory Glif!")
+impl<PD: PointData> TryFrom<&Glif<PD>> for Vec<u8> {
+    type Error = GlifParserError;
+    fn try_from(g: &Glif<PD>) -> Result<Self, GlifParserError> {
+        write_ufo_glif_data(g)
+    }
+}
+
+impl<PD: PointData> TryFrom<&Glif<PD>> for String {
+    type Error = GlifParserError;
+    fn try_from(g: &Glif<PD>) -> Result<Self, GlifParserError> {
+        write_ufo_glif(g)
     }
 }
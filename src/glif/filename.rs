@@ -0,0 +1,108 @@
+//! The UFO 3 user name to file name algorithm, which turns glyph and layer names into names safe
+//! on every file system, case-insensitive ones included.
+//!
+//! See <https://unifiedfontobject.org/versions/ufo3/conventions/#usernametofilename>.
+
+use std::collections::HashSet;
+
+/// File systems limit names to 255 bytes.
+const MAX_FILE_NAME_LENGTH: usize = 255;
+/// Length of the counter appended to names which clash with one already in use.
+const CLASH_COUNTER_LENGTH: usize = 15;
+
+const ILLEGAL_CHARACTERS: &[char] = &['"', '*', '+', '/', ':', '<', '>', '?', '[', '\\', ']', '|'];
+/// Names Windows reserves for devices, lowercased, exactly as the spec lists them. The spec also
+/// lists the drive names `A:` to `Z:`, but no part can match those once `:` has been replaced.
+const RESERVED_FILE_NAMES: &[&str] = &[
+    "con", "prn", "aux", "clock$", "nul", "com1", "lpt1", "lpt2", "lpt3", "com2", "com3", "com4",
+];
+
+/// Convert a user name (e.g. a glyph or layer name) to a file name between `prefix` and `suffix`,
+/// following the UFO 3 spec:
+///
+/// * a leading period is replaced by `_`, unless there's a prefix;
+/// * characters illegal on some file system are replaced by `_`, and uppercase letters are
+///   followed by `_`, so that `A` and `a` don't clash on case-insensitive file systems;
+/// * the name is clipped so that with `prefix` and `suffix` it's no more than 255 bytes;
+/// * each period-separated part that's a reserved Windows device name (`CON`, `aux`…) is
+///   prefixed with `_`;
+/// * if the result, lowercased, is in `existing`, a 15-digit counter is appended to the name, as
+///   low as makes it unique.
+///
+/// `existing` must hold the names already in use *lowercased*, as case-insensitive file systems
+/// compare them.
+pub fn user_name_to_file_name(name: &str, prefix: &str, suffix: &str, existing: &HashSet<String>) -> String {
+    let max_length = MAX_FILE_NAME_LENGTH.saturating_sub(prefix.len() + suffix.len());
+    let base = filtered(name, prefix.is_empty(), max_length);
+    let full_name = format!("{}{}{}", prefix, base, suffix);
+    if !existing.contains(&full_name.to_lowercase()) {
+        return full_name
+    }
+
+    // Each counter gives a different name, so one of the first `existing.len() + 1` is free.
+    let base = clip(&base, max_length.saturating_sub(CLASH_COUNTER_LENGTH));
+    (1u64..)
+        .map(|counter| format!("{}{}{:0width$}{}", prefix, base, counter, suffix, width = CLASH_COUNTER_LENGTH))
+        .find(|full_name| !existing.contains(&full_name.to_lowercase()))
+        .unwrap_or_default()
+}
+
+/// The .glif file name of the glyph named `name`, as [`user_name_to_file_name`] with no names in
+/// use. Without `append_extension`, returns just the part of the name derived from `name`.
+#[inline]
+pub fn name_to_filename(name: &str, append_extension: bool) -> String {
+    let suffix = if append_extension { ".glif" } else { "" };
+    user_name_to_file_name(name, "", suffix, &HashSet::new())
+}
+
+/// Whether `file_name` is one [`user_name_to_file_name`] could have given `name`, with or without
+/// a clash counter.
+pub(crate) fn is_file_name_of(file_name: &str, name: &str, prefix: &str, suffix: &str) -> bool {
+    let Some(base) = file_name.strip_prefix(prefix).and_then(|f| f.strip_suffix(suffix)) else { return false };
+    let max_length = MAX_FILE_NAME_LENGTH.saturating_sub(prefix.len() + suffix.len());
+    let filtered = filtered(name, prefix.is_empty(), max_length);
+    if base == filtered {
+        return true
+    }
+    let Some(counter_at) = base.len().checked_sub(CLASH_COUNTER_LENGTH) else { return false };
+    match (base.get(..counter_at), base.get(counter_at..)) {
+        (Some(clash_base), Some(counter)) => {
+            clash_base == clip(&filtered, max_length.saturating_sub(CLASH_COUNTER_LENGTH))
+                && counter.bytes().all(|b| b.is_ascii_digit())
+        },
+        _ => false,
+    }
+}
+
+/// Steps of the algorithm before clash handling.
+fn filtered(name: &str, no_prefix: bool, max_length: usize) -> String {
+    let mut ret = String::with_capacity(name.len() * 2);
+    for (i, c) in name.chars().enumerate() {
+        if (i == 0 && c == '.' && no_prefix) || ILLEGAL_CHARACTERS.contains(&c) || c.is_ascii_control() {
+            ret.push('_');
+        } else if c.to_lowercase().ne(std::iter::once(c)) {
+            ret.push(c);
+            ret.push('_');
+        } else {
+            ret.push(c);
+        }
+    }
+
+    clip(&ret, max_length)
+        .split('.')
+        .map(|part| match RESERVED_FILE_NAMES.contains(&part.to_lowercase().as_str()) {
+            true => format!("_{}", part),
+            false => part.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join(".")
+}
+
+/// `s`, clipped to at most `max_length` bytes without splitting a character.
+fn clip(s: &str, max_length: usize) -> &str {
+    let mut end = s.len().min(max_length);
+    while !s.is_char_boundary(end) {
+        end -= 1;
+    }
+    &s[..end]
+}
@@ -1,7 +1,18 @@
+use crate::color::Color;
+use crate::error::GlifParserError::{self, GlifInputError};
 use crate::xml;
 
+use std::str::FromStr as _;
+
 #[cfg(feature = "glifserde")]
-use serde::{Deserialize, Serialize};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+const MARK_COLOR: &str = "public.markColor";
+const VERTICAL_ORIGIN: &str = "public.verticalOrigin";
+const OBJECT_LIBS: &str = "public.objectLibs";
+
+/// Checks a `public.*` key's value is as the spec says.
+type Check = fn(&plist::Value) -> Result<(), GlifParserError>;
 
 #[cfg_attr(feature = "glifserde", derive(Serialize, Deserialize))]
 #[derive(Clone, Debug, PartialEq)]
@@ -20,3 +31,176 @@ impl Default for Lib {
         Self::None
     }
 }
+
+impl Lib {
+    /// The value of `key`, if any.
+    pub fn value(&self, key: &str) -> Option<&plist::Value> {
+        match self {
+            Lib::Plist(dict) => dict.get(key),
+            _ => None,
+        }
+    }
+
+    /// Set `key` to `value`, returning its old value. A [`Lib::None`] becomes a [`Lib::Plist`];
+    /// a [`Lib::Xml`] can't be set.
+    pub fn insert_value(&mut self, key: &str, value: plist::Value) -> Result<Option<plist::Value>, GlifParserError> {
+        if let Lib::None = self {
+            *self = Lib::Plist(plist::Dictionary::new());
+        }
+        match self {
+            Lib::Plist(dict) => Ok(dict.insert(key.to_string(), value)),
+            _ => Err(GlifParserError::GlifLibError),
+        }
+    }
+
+    /// Remove `key`, returning its value.
+    pub fn remove(&mut self, key: &str) -> Option<plist::Value> {
+        match self {
+            Lib::Plist(dict) => dict.remove(key),
+            _ => None,
+        }
+    }
+
+    /// The value of `key`, deserialized as a `T`. Fails with [`GlifParserError::GlifLibError`] if
+    /// it's not one.
+    #[cfg(feature = "glifserde")]
+    pub fn get<T: DeserializeOwned>(&self, key: &str) -> Result<Option<T>, GlifParserError> {
+        self.value(key).map(|value| plist::from_value(value)).transpose().map_err(GlifParserError::from)
+    }
+
+    /// Set `key` to `value`, serialized as plist.
+    #[cfg(feature = "glifserde")]
+    pub fn set<T: Serialize>(&mut self, key: &str, value: &T) -> Result<(), GlifParserError> {
+        let value = plist::to_value(value)?;
+        self.insert_value(key, value).map(|_| ())
+    }
+
+    /// `public.markColor`, the color the glyph is marked with in editors.
+    pub fn mark_color(&self) -> Option<Color> {
+        let value = self.value(MARK_COLOR)?;
+        match mark_color(value) {
+            Ok(color) => Some(color),
+            Err(_) => {
+                log::warn!("Ignoring public.markColor which is not a color: {:?}", value);
+                None
+            }
+        }
+    }
+
+    /// Set `public.markColor`, or remove it if `None`.
+    pub fn set_mark_color(&mut self, mark_color: Option<Color>) {
+        self.set_or_remove(MARK_COLOR, mark_color.map(|c| plist::Value::String(c.to_string())));
+    }
+
+    /// `public.verticalOrigin`, the y coordinate of the glyph's origin in vertical layout.
+    pub fn vertical_origin(&self) -> Option<f32> {
+        let value = self.value(VERTICAL_ORIGIN)?;
+        match vertical_origin(value) {
+            Ok(vo) => Some(vo),
+            Err(_) => {
+                log::warn!("Ignoring public.verticalOrigin which is not a number: {:?}", value);
+                None
+            }
+        }
+    }
+
+    /// Set `public.verticalOrigin`, or remove it if `None`. Integral values are written as
+    /// `<integer>`.
+    pub fn set_vertical_origin(&mut self, vertical_origin: Option<f32>) {
+        self.set_or_remove(VERTICAL_ORIGIN, vertical_origin.map(|vo| match vo.fract() == 0. {
+            true => plist::Value::Integer((vo as i64).into()),
+            false => plist::Value::Real(vo.into()),
+        }));
+    }
+
+    /// `public.objectLibs`: the libs of the glyph's points, contours, anchors, guidelines and
+    /// components, keyed by their identifiers. When read, each is moved onto its object, so this
+    /// only holds those whose identifier no object has.
+    pub fn object_libs(&self) -> Option<&plist::Dictionary> {
+        let value = self.value(OBJECT_LIBS)?;
+        match object_libs(value) {
+            Ok(object_libs) => Some(object_libs),
+            Err(_) => {
+                log::warn!("Ignoring public.objectLibs which is not a dictionary of dictionaries: {:?}", value);
+                None
+            }
+        }
+    }
+
+    /// The lib of the object with `identifier`, from `public.objectLibs`.
+    pub fn object_lib(&self, identifier: &str) -> Option<&plist::Dictionary> {
+        self.object_libs()?.get(identifier)?.as_dictionary()
+    }
+
+    /// Set the lib of the object with `identifier` in `public.objectLibs`, or remove it if `None`
+    /// or empty. `public.objectLibs` is removed when it's left empty.
+    pub fn set_object_lib(&mut self, identifier: &str, object_lib: Option<plist::Dictionary>) {
+        let mut object_libs = self.object_libs().cloned().unwrap_or_default();
+        match object_lib {
+            Some(object_lib) if !object_lib.is_empty() => { object_libs.insert(identifier.to_string(), plist::Value::Dictionary(object_lib)); },
+            _ => { object_libs.remove(identifier); },
+        }
+        self.set_or_remove(OBJECT_LIBS, (!object_libs.is_empty()).then_some(plist::Value::Dictionary(object_libs)));
+    }
+
+    /// Check the `public.*` keys the UFO spec defines for glyph libs have the types it gives.
+    pub fn validate(&self) -> Result<(), GlifParserError> {
+        match self.invalid_keys().first() {
+            Some((_, e)) => Err(e.clone()),
+            None => Ok(()),
+        }
+    }
+
+    /// Remove the keys [`Lib::validate`] objects to, returning their names.
+    pub(crate) fn remove_invalid(&mut self) -> Vec<&'static str> {
+        let invalid: Vec<_> = self.invalid_keys().into_iter().map(|(key, _)| key).collect();
+        for key in invalid.iter() {
+            self.remove(key);
+        }
+        invalid
+    }
+
+    fn invalid_keys(&self) -> Vec<(&'static str, GlifParserError)> {
+        let checks: [(&'static str, Check); 3] = [
+            (MARK_COLOR, |v| mark_color(v).map(|_| ())),
+            (VERTICAL_ORIGIN, |v| vertical_origin(v).map(|_| ())),
+            (OBJECT_LIBS, |v| object_libs(v).map(|_| ())),
+        ];
+        checks
+            .into_iter()
+            .filter_map(|(key, check)| Some((key, check(self.value(key)?).err()?)))
+            .collect()
+    }
+
+    fn set_or_remove(&mut self, key: &str, value: Option<plist::Value>) {
+        match value {
+            Some(value) => if self.insert_value(key, value).is_err() {
+                log::error!("Cannot set {} in a lib which failed to parse as a plist", key);
+            },
+            None => { self.remove(key); },
+        }
+    }
+}
+
+fn mark_color(value: &plist::Value) -> Result<Color, GlifParserError> {
+    match value.as_string() {
+        Some(s) => Color::from_str(s),
+        None => Err(GlifInputError(format!("{} is not a string", MARK_COLOR))),
+    }
+}
+
+fn vertical_origin(value: &plist::Value) -> Result<f32, GlifParserError> {
+    match value {
+        plist::Value::Integer(i) => i.as_signed().map(|i| i as f32).ok_or_else(|| GlifInputError(format!("{} is out of range", VERTICAL_ORIGIN))),
+        plist::Value::Real(f) => Ok(*f as f32),
+        _ => Err(GlifInputError(format!("{} is not a number", VERTICAL_ORIGIN))),
+    }
+}
+
+fn object_libs(value: &plist::Value) -> Result<&plist::Dictionary, GlifParserError> {
+    let object_libs = value.as_dictionary().ok_or_else(|| GlifInputError(format!("{} is not a dictionary", OBJECT_LIBS)))?;
+    match object_libs.iter().find(|(_, lib)| lib.as_dictionary().is_none()) {
+        Some((identifier, _)) => Err(GlifInputError(format!("{} of {} is not a dictionary", OBJECT_LIBS, identifier))),
+        None => Ok(object_libs),
+    }
+}
@@ -0,0 +1,339 @@
+//! Lossless round-tripping: a [`Glif`] kept alongside the .glif XML it was read from, so that
+//! writing it back rewrites only what was changed.
+
+use std::ops::{Deref, DerefMut, Range};
+
+use quick_xml::escape::escape;
+use quick_xml::events::Event;
+
+use super::read::read_ufo_glif_with_spans;
+use super::write::{check_outline, write_ufo_glif};
+use super::xml::Element;
+use super::{Glif, IntoXML};
+use crate::error::GlifParserError;
+use crate::pedantry::Pedantry;
+use crate::point::PointData;
+
+/// Where the elements glifparser read lie in a .glif's source, as byte ranges.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub(crate) struct SourceSpans {
+    /// The `<glyph>` start tag, or the whole element if `glyph_empty`
+    pub(crate) glyph: Range<usize>,
+    pub(crate) glyph_empty: bool,
+    pub(crate) format_1: bool,
+    /// Children of `<glyph>` that were read, in document order, with the index each of those
+    /// which may be many became in its `Vec` of [`Glif`], if it became anything
+    pub(crate) children: Vec<(&'static str, Option<usize>, Range<usize>)>,
+    /// Contours and components of `<outline>`, likewise, with the index each became in
+    /// [`Glif::outline`] or [`GlifComponents::vec`](crate::component::GlifComponents::vec)
+    pub(crate) outline_children: Vec<(&'static str, Option<usize>, Range<usize>)>,
+}
+
+/// A replacement of a byte range of the source
+type Edit = (Range<usize>, String);
+
+/// Children of `<glyph>`, each written from one part of a [`Glif`], in the order we write them.
+const SECTIONS: [&str; 8] = ["advance", "unicode", "anchor", "outline", "image", "guideline", "note", "lib"];
+
+/// Sections which may have many elements, each written from one item of a `Vec`
+const LIST_SECTIONS: [&str; 4] = ["unicode", "anchor", "image", "guideline"];
+
+/// A [`Glif`], along with the .glif XML it was read from. Edit the glif (it derefs to [`Glif`]);
+/// [`write_ufo_glif_lossless`] then rewrites only the elements whose data changed. Comments,
+/// formatting, element and attribute order and unknown elements are kept, and an unchanged glif is
+/// written back byte for byte, as read—including anything that was mended while reading it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LosslessGlif<PD: PointData> {
+    pub glif: Glif<PD>,
+    original: Glif<PD>,
+    source: String,
+    spans: SourceSpans,
+}
+
+impl<PD: PointData> Deref for LosslessGlif<PD> {
+    type Target = Glif<PD>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.glif
+    }
+}
+
+impl<PD: PointData> DerefMut for LosslessGlif<PD> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.glif
+    }
+}
+
+impl<PD: PointData> LosslessGlif<PD> {
+    /// The XML the glif was read from.
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    pub fn into_glif(self) -> Glif<PD> {
+        self.glif
+    }
+}
+
+impl<PD: PointData + PartialEq> LosslessGlif<PD> {
+    /// Whether the glif has been changed since it was read.
+    pub fn is_modified(&self) -> bool {
+        self.glif != self.original
+    }
+
+    /// Replacements of byte ranges of `source` which bring it up to date with `glif`, in order.
+    fn edits(&self) -> Result<Vec<Edit>, GlifParserError> {
+        let (source, spans) = (self.source.as_str(), &self.spans);
+        let mut edits = vec![];
+
+        if self.glif.name != self.original.name || self.glif.required_format_minor() != self.original.required_format_minor() {
+            edits.push((spans.glyph.clone(), self.glyph_start_tag()?));
+        }
+
+        check_outline(&self.glif)?;
+        let glyph = self.glif.xml();
+        let original = self.original.xml();
+        let default_indent = spans.children.first().map(|(_, _, span)| indentation(source, span.start)).unwrap_or("  ");
+        for (i, section) in SECTIONS.iter().enumerate() {
+            if !section_changed(section, &self.glif, &self.original) {
+                continue;
+            }
+            if let Some(element_edits) = self.element_edits(section, &glyph, &original)? {
+                edits.extend(element_edits);
+                continue;
+            }
+            let mut old = spans.children.iter().filter(|(name, _, _)| name == section).map(|(_, _, span)| span);
+            match old.next() {
+                Some(first) => {
+                    let written = write_section(&glyph, section, indentation(source, first.start))?;
+                    if written.is_empty() {
+                        edits.push((whole_lines(source, first), written));
+                    } else {
+                        edits.push((first.clone(), written));
+                    }
+                    edits.extend(old.map(|span| (whole_lines(source, span), String::new())));
+                },
+                None => {
+                    let written = write_section(&glyph, section, default_indent)?;
+                    if written.is_empty() {
+                        continue;
+                    }
+                    // After the last element of an earlier section, if any
+                    let at = spans.children.iter()
+                        .rfind(|(name, _, _)| SECTIONS[..i].contains(name))
+                        .map(|(_, _, span)| span.end)
+                        .unwrap_or(spans.glyph.end);
+                    edits.push((at..at, format!("\n{}{}", default_indent, written)));
+                },
+            }
+        }
+
+        edits.sort_by_key(|(range, _)| (range.start, range.end));
+        Ok(edits)
+    }
+
+    /// Edits to the elements of a section which has some both in the source and as written now,
+    /// replacing only those whose XML changed. `None` if the section must be rewritten whole.
+    fn element_edits(&self, section: &str, glyph: &Element, original: &Element) -> Result<Option<Vec<Edit>>, GlifParserError> {
+        if LIST_SECTIONS.contains(&section) {
+            let old: Vec<_> = self.spans.children.iter()
+                .filter(|(name, _, _)| *name == section)
+                .map(|(_, idx, span)| (*idx, span.clone()))
+                .collect();
+            let new = write_elements(glyph, section)?;
+            let Some((_, last)) = old.last() else { return Ok(None) };
+            if new.is_empty() {
+                return Ok(None)
+            }
+            return Ok(Some(self.patch_elements(&old, &write_elements(original, section)?, &new, (last.end, false))))
+        }
+
+        if section != "outline" {
+            return Ok(None)
+        }
+        let (Some(outline), Some(original_outline)) = (child(glyph, "outline"), child(original, "outline")) else { return Ok(None) };
+        let Some((_, _, first)) = self.spans.outline_children.first() else { return Ok(None) };
+        let mut edits = vec![];
+        // New contours go after the last contour, or before the first component; new components
+        // after the last component, or the last contour.
+        let mut at = (first.start, true);
+        for name in ["contour", "component"] {
+            let old: Vec<_> = self.spans.outline_children.iter()
+                .filter(|(n, _, _)| *n == name)
+                .map(|(_, idx, span)| (*idx, span.clone()))
+                .collect();
+            if let Some((_, last)) = old.last() {
+                at = (last.end, false);
+            }
+            edits.extend(self.patch_elements(&old, &write_elements(original_outline, name)?, &write_elements(outline, name)?, at));
+        }
+        Ok(Some(edits))
+    }
+
+    /// Replace each `old` element, given with the index of the item it was read as, whose item
+    /// was `written` differently from how it's written (`new`) now, and remove those past the end
+    /// of `new`. The rest of `new` is added at `at.0`; after what's there, or before it if `at.1`.
+    fn patch_elements(&self, old: &[(Option<usize>, Range<usize>)], written: &[String], new: &[String], at: (usize, bool)) -> Vec<Edit> {
+        let source = self.source.as_str();
+        let mut edits = vec![];
+        for (idx, span) in old {
+            let Some(idx) = *idx else { continue };
+            match new.get(idx) {
+                Some(new) if written.get(idx) == Some(new) => {},
+                Some(new) => edits.push((span.clone(), new.replace('\n', &format!("\n{}", indentation(source, span.start))))),
+                None => edits.push((whole_lines(source, span), String::new())),
+            }
+        }
+
+        let (at, before) = at;
+        let at = if before { at } else { line_end(source, at) };
+        let indent = old.first().map(|(_, span)| indentation(source, span.start)).unwrap_or_else(|| indentation(source, at));
+        let read = old.iter().filter(|(idx, _)| idx.is_some()).count();
+        for added in new.iter().skip(read) {
+            let added = added.replace('\n', &format!("\n{}", indent));
+            edits.push((at..at, if before { format!("{}\n{}", added, indent) } else { format!("\n{}{}", indent, added) }));
+        }
+        edits
+    }
+
+    /// The original `<glyph>` start tag, with its name and formatMinor updated.
+    fn glyph_start_tag(&self) -> Result<String, GlifParserError> {
+        let tag = &self.source[self.spans.glyph.clone()];
+        let mut reader = quick_xml::Reader::from_str(tag);
+        let Event::Start(start) = reader.read_event()? else {
+            return Err(GlifParserError::XmlWriteError(String::from("Original <glyph> start tag unreadable")))
+        };
+
+        let format_minor = self.glif.required_format_minor();
+        let format_minor = (format_minor != 0).then(|| format_minor.to_string());
+        let had_format_minor = start.try_get_attribute("formatMinor")?.is_some();
+        let mut attributes: Vec<(String, String)> = vec![];
+        for attribute in start.attributes() {
+            let attribute = attribute?;
+            let key = String::from_utf8_lossy(attribute.key.as_ref()).into_owned();
+            let value = match key.as_str() {
+                "name" => self.glif.name.clone(),
+                "formatMinor" => match &format_minor {
+                    Some(format_minor) => format_minor.clone(),
+                    None => continue,
+                },
+                _ => attribute.unescape_value()?.into_owned(),
+            };
+            let is_format = key == "format";
+            attributes.push((key, value));
+            if let (true, false, Some(format_minor)) = (is_format, had_format_minor, &format_minor) {
+                attributes.push((String::from("formatMinor"), format_minor.clone()));
+            }
+        }
+
+        let attributes: String = attributes.iter().map(|(k, v)| format!(" {}=\"{}\"", k, escape(v))).collect();
+        Ok(format!("<glyph{}>", attributes))
+    }
+}
+
+fn section_changed<PD: PointData + PartialEq>(section: &str, glif: &Glif<PD>, original: &Glif<PD>) -> bool {
+    match section {
+        "advance" => (glif.width, glif.height) != (original.width, original.height),
+        "unicode" => glif.unicode != original.unicode,
+        "anchor" => glif.anchors != original.anchors,
+        "outline" => glif.outline != original.outline || glif.components.vec != original.components.vec,
+        #[cfg(feature = "glifimage")]
+        "image" => glif.images != original.images,
+        "guideline" => glif.guidelines != original.guidelines,
+        "note" => glif.note != original.note,
+        "lib" => glif.lib_with_object_data() != original.lib_with_object_data(),
+        _ => false,
+    }
+}
+
+/// The `name` children of `parent` as XML, each on lines of its own, unindented.
+fn write_elements(parent: &Element, name: &str) -> Result<Vec<String>, GlifParserError> {
+    let config = xmltree::EmitterConfig::new()
+        .perform_indent(true)
+        .pad_self_closing(false)
+        .autopad_comments(false)
+        .write_document_declaration(false);
+
+    let mut written = vec![];
+    for el in parent.children.iter().filter_map(|c| c.as_element()).filter(|el| el.name == name) {
+        let mut buf: Vec<u8> = vec![];
+        el.write_with_config(&mut buf, config.clone())?;
+        written.push(String::from_utf8(buf)?);
+    }
+    Ok(written)
+}
+
+/// The `section` children of `glyph` as XML, one per line, each line after the first indented by
+/// `indent`.
+fn write_section(glyph: &Element, section: &str, indent: &str) -> Result<String, GlifParserError> {
+    Ok(write_elements(glyph, section)?.join("\n").replace('\n', &format!("\n{}", indent)))
+}
+
+/// The first `name` child of `parent`
+fn child<'a>(parent: &'a Element, name: &str) -> Option<&'a Element> {
+    parent.children.iter().filter_map(|c| c.as_element()).find(|el| el.name == name)
+}
+
+/// The whitespace `pos` is indented by, or nothing if it isn't the first thing on its line.
+fn indentation(source: &str, pos: usize) -> &str {
+    let line_start = source[..pos].rfind('\n').map(|i| i + 1).unwrap_or(0);
+    let indent = &source[line_start..pos];
+    if indent.chars().all(|c| c == ' ' || c == '\t') { indent } else { "" }
+}
+
+/// The end of `pos`'s line, if only whitespace or a comment follows it there, else `pos`.
+fn line_end(source: &str, pos: usize) -> usize {
+    let end = source[pos..].find(['\r', '\n']).map(|i| pos + i).unwrap_or(source.len());
+    let rest = source[pos..end].trim();
+    if rest.is_empty() || (rest.starts_with("<!--") && rest.find("-->") == Some(rest.len() - 3)) {
+        end
+    } else {
+        pos
+    }
+}
+
+/// `span`, extended to take its line break and indentation if it's the first thing on its line,
+/// so that removing it leaves no blank line.
+fn whole_lines(source: &str, span: &Range<usize>) -> Range<usize> {
+    match source[..span.start].rfind('\n') {
+        Some(newline) if source[newline + 1..span.start].chars().all(|c| c == ' ' || c == '\t') => {
+            let start = if source[..newline].ends_with('\r') { newline - 1 } else { newline };
+            start..span.end
+        },
+        _ => span.clone(),
+    }
+}
+
+/// Read UFO .glif XML, keeping it alongside the [`Glif`] so it can be written back losslessly.
+pub fn read_ufo_glif_lossless<PD: PointData>(glif: &str) -> Result<LosslessGlif<PD>, GlifParserError> {
+    read_ufo_glif_lossless_pedantic(glif, Pedantry::default())
+}
+
+pub fn read_ufo_glif_lossless_pedantic<PD: PointData>(glif: &str, pedantry: Pedantry) -> Result<LosslessGlif<PD>, GlifParserError> {
+    let (read, spans) = read_ufo_glif_with_spans(glif, pedantry)?;
+    Ok(LosslessGlif { original: read.clone(), glif: read, source: glif.to_string(), spans })
+}
+
+/// Write a [`LosslessGlif`] back to XML, patching its source only where the glif was changed. A
+/// changed GLIF format 1 glyph, or `<glyph/>` with no content, is written afresh as format 2.
+pub fn write_ufo_glif_lossless<PD: PointData + PartialEq>(glif: &LosslessGlif<PD>) -> Result<String, GlifParserError> {
+    let edits = glif.edits()?;
+    if edits.is_empty() {
+        return Ok(glif.source.clone())
+    }
+    if glif.spans.format_1 || glif.spans.glyph_empty {
+        return write_ufo_glif(&glif.glif)
+    }
+
+    let source = glif.source.as_str();
+    let mut ret = String::with_capacity(source.len());
+    let mut pos = 0;
+    for (range, text) in edits {
+        ret.push_str(&source[pos..range.start]);
+        ret.push_str(&text);
+        pos = range.end;
+    }
+    ret.push_str(&source[pos..]);
+
+    Ok(ret)
+}
@@ -0,0 +1,39 @@
+//! [`Bounds`] of MFEK contours and layers.
+
+use kurbo::Rect;
+
+use super::contour::MFEKContourCommon as _;
+use super::inner::MFEKContourInner;
+use super::{Layer, MFEKContour};
+use crate::outline::{point_bounds, union_bounds, Bounds};
+use crate::point::{PointData, WhichHandle};
+
+/// Only cubic contours have exact bounds; those of quadratic and hyperbezier contours are their
+/// control boxes.
+impl<PD: PointData> Bounds for MFEKContour<PD> {
+    fn bounds(&self) -> Option<Rect> {
+        match self.inner() {
+            MFEKContourInner::Cubic(contour) => contour.bounds(),
+            _ => self.control_bounds(),
+        }
+    }
+
+    fn control_bounds(&self) -> Option<Rect> {
+        let inner = self.inner();
+        let points = (0..inner.len()).filter_map(|i| inner.get_point(i));
+        point_bounds(points.flat_map(|p| {
+            let handles = [WhichHandle::A, WhichHandle::B].into_iter().filter_map(|wh| p.get_handle_position(wh));
+            std::iter::once(p.get_position()).chain(handles).collect::<Vec<_>>()
+        }))
+    }
+}
+
+impl<PD: PointData> Bounds for Layer<PD> {
+    fn bounds(&self) -> Option<Rect> {
+        union_bounds(self.outline.iter().filter_map(|c| c.bounds()))
+    }
+
+    fn control_bounds(&self) -> Option<Rect> {
+        union_bounds(self.outline.iter().filter_map(|c| c.control_bounds()))
+    }
+}
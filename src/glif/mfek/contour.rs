@@ -1,9 +1,10 @@
 
-use core::panic;
 
 use serde::{Serialize, Deserialize};
 use crate::PointData;
 use crate::Point;
+use crate::outline::Contour;
+use crate::error::GlifParserError;
 
 use super::contour_operations::{ContourOperations, ContourOperation};
 use super::inner::MFEKCommonInner;
@@ -19,6 +20,12 @@ use super::point::MFEKPointCommon;
 pub struct MFEKContour<PD: PointData> {
     inner: MFEKContourInner<PD>,
     pub operation: Option<ContourOperations<PD>>,
+    /// .glif `<contour identifier>`
+    #[serde(default)]
+    pub identifier: Option<String>,
+    /// The contour's lib in `public.objectLibs`
+    #[serde(default)]
+    pub lib: plist::Dictionary,
 }
 
 impl<PD: PointData> MFEKContour<PD> {
@@ -26,6 +33,8 @@ impl<PD: PointData> MFEKContour<PD> {
         MFEKContour {
             inner,
             operation,
+            identifier: None,
+            lib: plist::Dictionary::new(),
         }
     }
 
@@ -94,6 +103,26 @@ impl<PD: PointData> From<Vec<Point<PD>>> for MFEKContour<PD> {
     }
 }
 
+impl<PD: PointData> From<&Contour<PD>> for MFEKContour<PD> {
+    fn from(contour: &Contour<PD>) -> Self {
+        Self {
+            identifier: contour.identifier.clone(),
+            lib: contour.lib.clone(),
+            ..Self::from(&contour.points)
+        }
+    }
+}
+
+impl<PD: PointData> From<Contour<PD>> for MFEKContour<PD> {
+    fn from(contour: Contour<PD>) -> Self {
+        Self {
+            identifier: contour.identifier,
+            lib: contour.lib,
+            ..Self::from(contour.points)
+        }
+    }
+}
+
 pub struct MFEKContourCommonIterator<'a, PD: PointData> {
     pub index: usize,
     pub contour: &'a dyn MFEKContourCommon<PD>
@@ -310,16 +339,24 @@ impl<PD: PointData> MFEKContourCommon<PD> for MFEKCubicContour<PD> {
 }
 
 
-impl<PD: PointData> From<&MFEKContourInner<PD>> for Vec<Point<PD>> {
-    fn from(contour: &MFEKContourInner<PD>) -> Vec<Point<PD>> {
-        match contour{
-            MFEKContourInner::Cubic(contour) => {return contour.clone()}
-            // TODO: Better handling
-            _ => panic!("Can't turn a mixed contour into Vec<Point<PD>>!")
+impl<PD: PointData> TryFrom<&MFEKContourInner<PD>> for Vec<Point<PD>> {
+    type Error = GlifParserError;
+    fn try_from(contour: &MFEKContourInner<PD>) -> Result<Vec<Point<PD>>, GlifParserError> {
+        match contour {
+            MFEKContourInner::Cubic(contour) => Ok(contour.clone()),
+            _ => Err(GlifParserError::TypeConversionError { req_type: "MFEKContourInner", req_variant: String::from("Cubic") }),
         }
     }
 }
 
+impl<PD: PointData> TryFrom<&MFEKContour<PD>> for Contour<PD> {
+    type Error = GlifParserError;
+    fn try_from(contour: &MFEKContour<PD>) -> Result<Contour<PD>, GlifParserError> {
+        let points: Vec<Point<PD>> = contour.inner().try_into()?;
+        Ok(Contour { lib: contour.lib.clone(), ..Contour::from(points).identifier(contour.identifier.clone()) })
+    }
+}
+
 impl<PD: PointData> From<&Vec<Point<PD>>> for MFEKContourInner<PD> {
     fn from(contour: &Vec<Point<PD>>) -> MFEKContourInner<PD> {
         MFEKContourInner::Cubic(contour.clone())
@@ -3,9 +3,11 @@ use serde::Deserialize;
 use kurbo::Affine;
 use plist;
 
+use std::collections::HashSet;
+
 use super::{MFEKOutline, LayerOperation};
 use crate::color::Color;
-use crate::glif::name_to_filename;
+use crate::glif::user_name_to_file_name;
 use crate::image::GlifImage;
 use crate::point::PointData;
 
@@ -23,11 +25,13 @@ pub struct Layer<PD: PointData> {
 }
 
 impl<PD: PointData> Layer<PD> {
-    pub fn to_glyphs_dir(&self, idx: usize) -> String {
+    /// The layer's glyphs directory: `glyphs` for the first layer, otherwise one named by the UFO 3
+    /// file name algorithm, unique among the lowercased directory names in `existing`.
+    pub fn to_glyphs_dir(&self, idx: usize, existing: &HashSet<String>) -> String {
         if idx == 0 {
             String::from("glyphs")
         } else {
-            format!("glyphs.{}", name_to_filename(&self.name, false))
+            user_name_to_file_name(&self.name, "glyphs.", "", existing)
         }
     }
 }
@@ -72,6 +76,7 @@ impl<PD: PointData> ToLayerInfoPlist for Layer<PD> {
 impl<PD: PointData> ToLayerContentsPlist for &[Layer<PD>] {
     fn to_layercontents_plist(&self) -> plist::Value {
         let mut ret: Vec<plist::Value> = Vec::new();
+        let mut dirs = HashSet::new();
 
         for (i, layer) in self.iter().enumerate() {
             if !layer.visible { continue }
@@ -80,7 +85,8 @@ impl<PD: PointData> ToLayerContentsPlist for &[Layer<PD>] {
             } else {
                 layer.name.clone()
             };
-            let value = layer.to_glyphs_dir(i);
+            let value = layer.to_glyphs_dir(i, &dirs);
+            dirs.insert(value.to_lowercase());
             ret.push(plist::Value::Array(vec![plist::Value::String(key), plist::Value::String(value)]));
         }
 
@@ -1,4 +1,3 @@
-use core::panic;
 use std::collections::HashSet;
 use std::path as stdpath;
 
@@ -10,6 +9,7 @@ use serde::{Serialize, Deserialize};
 use crate::{PointType, Point};
 use crate::anchor::Anchor;
 use crate::component::{ComponentRect, GlifComponents};
+use crate::error::GlifParserError;
 use crate::glif::Glif;
 use crate::guideline::Guideline;
 use crate::outline::Outline;
@@ -18,6 +18,7 @@ use crate::outline::skia::{SkiaPaths, SkiaPointTransforms, ToSkiaPath, ToSkiaPat
 use crate::point::PointData;
 
 #[macro_use] pub mod layer;
+mod bounds;
 pub use layer::Layer;
 pub(crate) use DEFAULT_LAYER_FORMAT_STR;
 pub mod traits;
@@ -42,6 +43,9 @@ pub struct MFEKGlif<PD: PointData> {
     pub component_rects: Option<Vec<ComponentRect>>, // holds cached flattened component rects
     pub guidelines: Vec<Guideline<PD>>,
     pub width: Option<u64>,
+    pub height: Option<u64>,
+    /// `public.verticalOrigin` from the glif lib
+    pub vertical_origin: Option<f32>,
     pub unicode: Vec<char>,
     pub name: String,
     /// This is an arbitrary glyph comment, exactly like the comment field in FontForge SFD.
@@ -65,6 +69,8 @@ impl<PD: PointData> From<Glif<PD>> for MFEKGlif<PD> {
             components: glif.components,
             guidelines: glif.guidelines,
             width: glif.width,
+            height: glif.height,
+            vertical_origin: glif.lib.vertical_origin(),
             unicode: glif.unicode,
             name: glif.name,
             note: glif.note,
@@ -90,34 +96,35 @@ impl<PD: PointData> From<Glif<PD>> for MFEKGlif<PD> {
     }
 }
 
-impl<PD: PointData> From<MFEKGlif<PD>> for Glif<PD> {
-    fn from(glif: MFEKGlif<PD>) -> Self {
-        let outline = glif.layers[0].outline.iter().map(|contour| 
-            match &contour.inner() {
-                MFEKContourInner::Cubic(cubic) => cubic.clone(),
-                // TODO: BETTER HANDLING!
-                _ => panic!("Tried to convert non-cubic MFEKGlif to glif")
-            }
-            
-        ).collect();
-
-        let images = glif.layers[0].images.iter().map(|tupes| {
-            tupes.0.clone()
-        }).collect();
+impl<PD: PointData> TryFrom<MFEKGlif<PD>> for Glif<PD> {
+    type Error = GlifParserError;
+
+    /// Only the first layer is converted, and only if all its contours are cubic.
+    fn try_from(glif: MFEKGlif<PD>) -> Result<Self, GlifParserError> {
+        let (outline, images) = match glif.layers.first() {
+            Some(layer) => (
+                Some(layer.outline.iter().map(|contour| contour.try_into()).collect::<Result<_, _>>()?),
+                layer.images.iter().map(|tupes| tupes.0.clone()).collect(),
+            ),
+            None => (None, vec![]),
+        };
 
-        Glif {
+        let mut ret = Glif {
             anchors: glif.anchors.clone(),
             components: glif.components.clone(),
             guidelines: glif.guidelines.clone(),
             width: glif.width,
+            height: glif.height,
             unicode: glif.unicode.clone(),
             name: glif.name.clone(),
             filename: glif.filename.clone(),
-            outline: Some(outline),
+            outline,
             images,
             note: glif.note.clone(),
             ..Glif::default()
-        }
+        };
+        ret.lib.set_vertical_origin(glif.vertical_origin);
+        Ok(ret)
     }
 }
 
@@ -156,7 +163,7 @@ impl<PD: PointData> ToSkiaPaths for MFEKOutline<PD> {
                         &mut closed
                     }.add_path(&skpath, (0., 0.), skia::path::AddPathMode::Append);
                 },
-                _ => { panic!("Attempted to to_skia_paths a mixed contour!") }
+                _ => log::error!("Skipped non-cubic contour in to_skia_paths, convert it to cubic first"),
             }
         }
 
@@ -181,6 +188,7 @@ pub enum LayerOperation {
     Intersect,
 }
 
+#[cfg(feature = "skia")]
 use self::inner::MFEKContourInner;
 
 use super::GlifLike;
@@ -1,5 +1,5 @@
 use super::{contour_operations::ContourOperations, inner::MFEKContourInner, MFEKOutline};
-use crate::{outline, point, PointData, error::mfek::UpgradeContourOpsError};
+use crate::{outline, point, PointData, error::GlifParserError, error::mfek::UpgradeContourOpsError};
 use crate::{Outline, MFEKGlif};
 
 use std::collections::VecDeque;
@@ -17,7 +17,8 @@ impl<PD: PointData> PointData for MFEKOutlineContourOperations<PD> {}
 impl<PD: PointData> PointData for MFEKGlifContourOperations<PD> {}
 
 pub trait DowngradeOutline<PD: point::PointData> {
-    fn downgrade(self) -> outline::Outline<PD>;
+    /// Errors if any contour isn't cubic; check [`Self::cleanly_downgradable`] first.
+    fn downgrade(self) -> Result<outline::Outline<PD>, GlifParserError>;
     fn cleanly_downgradable(&self) -> bool;
 }
 
@@ -45,17 +46,8 @@ impl<PD: PointData> DowngradeOutline<PD> for MFEKOutline<PD> {
         self.iter().all(|c| c.operation().is_none())
     }
 
-    fn downgrade(self) -> Outline<PD> {
-        let mut ret = Outline::new();
-        for contour in self {
-            match contour.inner() {
-                MFEKContourInner::Cubic(cubic_contour) => {
-                    ret.push(cubic_contour.clone());
-                }
-                _ => panic!("Tried to downgrade a non-cubic glyph!")
-            }
-        }
-        ret
+    fn downgrade(self) -> Result<Outline<PD>, GlifParserError> {
+        self.iter().map(|contour| contour.try_into()).collect()
     }
 }
 
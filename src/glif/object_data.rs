@@ -0,0 +1,279 @@
+//! Per-object libs, which .glif files keep in `public.objectLibs` keyed by identifier, but which
+//! [`Glif`] keeps on the objects themselves, along with any [`PointData`] which opts in to being
+//! written there; see [`PointData::OBJECT_LIB`].
+
+use std::borrow::Cow;
+#[cfg(feature = "glifserde")]
+use std::collections::HashSet;
+
+use super::{Glif, IntoXML, Lib};
+use crate::outline::Contour;
+use crate::point::{Handle, PointData};
+use crate::xml::Element;
+
+/// The data an object has, which is kept in its lib.
+#[cfg(feature = "glifserde")]
+enum ObjectData<'a, PD> {
+    /// A point's, which it may not have
+    Point(&'a mut Option<PD>),
+    /// An anchor's or guideline's, which is written unless it's the default
+    Always(&'a mut PD),
+    None,
+}
+
+/// An object's lib, which points and their handles only have if it's not empty.
+#[cfg(feature = "glifserde")]
+enum LibMut<'a> {
+    Always(&'a mut plist::Dictionary),
+    Optional(&'a mut Option<plist::Dictionary>),
+}
+
+#[cfg(feature = "glifserde")]
+impl LibMut<'_> {
+    fn set(&mut self, lib: plist::Dictionary) {
+        match self {
+            LibMut::Always(l) => **l = lib,
+            LibMut::Optional(l) => **l = Some(lib).filter(|lib| !lib.is_empty()),
+        }
+    }
+}
+
+#[cfg(feature = "glifserde")]
+fn non_empty(lib: &plist::Dictionary) -> Option<&plist::Dictionary> {
+    Some(lib).filter(|lib| !lib.is_empty())
+}
+
+#[cfg(feature = "glifserde")]
+impl<PD: PointData> ObjectData<'_, PD> {
+    fn set(&mut self, pd: PD) {
+        match self {
+            ObjectData::Point(data) => **data = Some(pd),
+            ObjectData::Always(data) => **data = pd,
+            ObjectData::None => {},
+        }
+    }
+}
+
+impl<PD: PointData> Glif<PD> {
+    /// The lib as written to .glif files: [`Glif::lib`], with the libs of contours, points,
+    /// components, anchors and guidelines in `public.objectLibs`, along with their data if `PD`
+    /// opts in to it.
+    #[cfg(feature = "glifserde")]
+    pub fn lib_with_object_data(&self) -> Cow<'_, Lib> {
+        let objects = self.objects();
+        if objects.iter().all(|(_, lib, data)| lib.is_none() && data.is_none()) {
+            return Cow::Borrowed(&self.lib)
+        }
+
+        let generated = self.generate_identifiers(&objects);
+        let mut lib = self.lib.clone();
+        for ((identifier, object_lib, data), generated) in objects.into_iter().zip(generated) {
+            let Some(identifier) = identifier.as_ref().or(generated.as_ref()) else { continue };
+            let mut object_lib = object_lib.cloned().unwrap_or_default();
+            if let (Some(object_lib_data), Some(value)) = (PD::OBJECT_LIB, data) {
+                object_lib.insert(object_lib_data.key.to_string(), value);
+            }
+            if !object_lib.is_empty() {
+                lib.set_object_lib(identifier, Some(object_lib));
+            }
+        }
+        Cow::Owned(lib)
+    }
+
+    #[cfg(not(feature = "glifserde"))]
+    pub fn lib_with_object_data(&self) -> Cow<'_, Lib> {
+        Cow::Borrowed(&self.lib)
+    }
+
+    /// Identifiers for the objects which have a lib or data to write to `public.objectLibs` but no
+    /// identifier to key it by, to be given to them as the glif is written.
+    #[cfg(feature = "glifserde")]
+    pub(crate) fn generated_identifiers(&self) -> GeneratedIdentifiers {
+        GeneratedIdentifiers(self.generate_identifiers(&self.objects()).into_iter())
+    }
+
+    #[cfg(not(feature = "glifserde"))]
+    pub(crate) fn generated_identifiers(&self) -> GeneratedIdentifiers {
+        GeneratedIdentifiers(vec![].into_iter())
+    }
+
+    /// For each of `objects`, the identifier it's given, if it needs one.
+    #[cfg(feature = "glifserde")]
+    fn generate_identifiers(&self, objects: &[(&Option<String>, Option<&plist::Dictionary>, Option<plist::Value>)]) -> Vec<Option<String>> {
+        let mut taken: HashSet<&str> = objects.iter().filter_map(|(identifier, _, _)| identifier.as_deref()).collect();
+        if let Some(object_libs) = self.lib.object_libs() {
+            taken.extend(object_libs.keys().map(String::as_str));
+        }
+        let mut n = 0;
+        objects.iter().map(|(identifier, lib, data)| {
+            if identifier.is_some() || (lib.is_none() && data.is_none()) {
+                return None
+            }
+            while taken.contains(format!("id{}", n).as_str()) {
+                n += 1;
+            }
+            n += 1;
+            Some(format!("id{}", n - 1))
+        }).collect()
+    }
+
+    /// Each object which can have a lib, in the order they're written, with its identifier, its
+    /// lib if it's not empty and its data as it's written there, if any.
+    #[cfg(feature = "glifserde")]
+    fn objects(&self) -> Vec<(&Option<String>, Option<&plist::Dictionary>, Option<plist::Value>)> {
+        let value = |pd: &PD| {
+            PD::OBJECT_LIB?;
+            plist::to_value(pd).map_err(|e| log::error!("Failed to write point data in {} as plist: {:?}", self.name, e)).ok()
+        };
+        let default = value(&PD::default());
+
+        let mut ret = vec![];
+        for anchor in self.anchors.iter() {
+            ret.push((&anchor.identifier, non_empty(&anchor.lib), value(&anchor.data).filter(|v| Some(v) != default.as_ref())));
+        }
+        for contour in self.outline.iter().flatten() {
+            ret.push((&contour.identifier, non_empty(&contour.lib), None));
+            for point in contour.iter() {
+                ret.push((&point.identifier, point.lib.as_ref().and_then(non_empty), point.data.as_ref().and_then(value)));
+                for (handle, attributes) in [(point.a, &point.a_attributes), (point.b, &point.b_attributes)] {
+                    if let Handle::At(..) = handle {
+                        ret.push((&attributes.identifier, attributes.lib.as_ref().and_then(non_empty), None));
+                    }
+                }
+            }
+        }
+        for component in self.components.vec.iter() {
+            ret.push((&component.identifier, non_empty(&component.lib), None));
+        }
+        for guideline in self.guidelines.iter() {
+            ret.push((&guideline.identifier, non_empty(&guideline.lib), value(&guideline.data).filter(|v| Some(v) != default.as_ref())));
+        }
+        ret
+    }
+
+    /// As [`Glif::objects`], but with the objects' data rather than how it's written.
+    #[cfg(feature = "glifserde")]
+    fn objects_mut(&mut self) -> Vec<(&mut Option<String>, LibMut<'_>, ObjectData<'_, PD>)> {
+        let mut ret = vec![];
+        for anchor in self.anchors.iter_mut() {
+            ret.push((&mut anchor.identifier, LibMut::Always(&mut anchor.lib), ObjectData::Always(&mut anchor.data)));
+        }
+        for contour in self.outline.iter_mut().flatten() {
+            ret.push((&mut contour.identifier, LibMut::Always(&mut contour.lib), ObjectData::None));
+            for point in contour.points.iter_mut() {
+                ret.push((&mut point.identifier, LibMut::Optional(&mut point.lib), ObjectData::Point(&mut point.data)));
+                for (handle, attributes) in [(point.a, &mut point.a_attributes), (point.b, &mut point.b_attributes)] {
+                    if let Handle::At(..) = handle {
+                        ret.push((&mut attributes.identifier, LibMut::Optional(&mut attributes.lib), ObjectData::None));
+                    }
+                }
+            }
+        }
+        for component in self.components.vec.iter_mut() {
+            ret.push((&mut component.identifier, LibMut::Always(&mut component.lib), ObjectData::None));
+        }
+        for guideline in self.guidelines.iter_mut() {
+            ret.push((&mut guideline.identifier, LibMut::Always(&mut guideline.lib), ObjectData::Always(&mut guideline.data)));
+        }
+        ret
+    }
+
+    /// Move the libs in `public.objectLibs` onto the objects they belong to, and data from them
+    /// into the objects' data, returning warnings about any data which can't be read. The libs of
+    /// identifiers no object has are left be.
+    #[cfg(feature = "glifserde")]
+    pub(crate) fn take_object_libs(&mut self) -> Vec<String> {
+        let Some(object_libs) = self.lib.object_libs().cloned() else { return vec![] };
+
+        let mut taken = vec![];
+        let mut warnings = vec![];
+        for (identifier, mut lib, mut data) in self.objects_mut() {
+            let Some(identifier) = identifier.as_ref() else { continue };
+            let Some(object_lib) = object_libs.get(identifier).and_then(|l| l.as_dictionary()) else { continue };
+            let mut object_lib = object_lib.clone();
+            taken.push(identifier.clone());
+
+            let has_data = !matches!(data, ObjectData::None);
+            if let (Some(object_lib_data), true) = (PD::OBJECT_LIB, has_data) {
+                let key = object_lib_data.key;
+                match object_lib.get(key).map(|value| object_lib_data.read(value)) {
+                    Some(Ok(pd)) => {
+                        data.set(pd);
+                        object_lib.remove(key);
+                    },
+                    Some(Err(e)) => warnings.push(format!("Failed to read {} of {} in public.objectLibs: {}", key, identifier, e)),
+                    None => {},
+                }
+            }
+            lib.set(object_lib);
+        }
+
+        for identifier in taken {
+            self.lib.set_object_lib(&identifier, None);
+        }
+        if matches!(&self.lib, Lib::Plist(dict) if dict.is_empty()) {
+            self.lib = Lib::None;
+        }
+        warnings
+    }
+}
+
+/// Identifiers generated for objects which need one, given to them in turn as the glif is written.
+/// See [`Glif::generated_identifiers`].
+pub(crate) struct GeneratedIdentifiers(std::vec::IntoIter<Option<String>>);
+
+impl GeneratedIdentifiers {
+    fn next(&mut self) -> Option<String> {
+        self.0.next().flatten()
+    }
+
+    /// The next object's element, with its generated identifier if it needs one.
+    pub(crate) fn element(&mut self, mut el: Element) -> Element {
+        if let Some(identifier) = self.next() {
+            el.attributes.insert("identifier".to_owned(), identifier);
+        }
+        el
+    }
+
+    /// The element of the next contour, with its and its points' generated identifiers.
+    pub(crate) fn contour<PD: PointData>(&mut self, contour: &Contour<PD>) -> Element {
+        let generated: Vec<Option<String>> = contour_identifiers(contour).iter().map(|_| self.next()).collect();
+        if generated.iter().all(Option::is_none) {
+            return contour.xml()
+        }
+        let mut contour = contour.clone();
+        for (identifier, generated) in contour_identifiers_mut(&mut contour).into_iter().zip(generated) {
+            if generated.is_some() {
+                *identifier = generated;
+            }
+        }
+        contour.into_xml()
+    }
+}
+
+/// The identifiers of a contour, its points and their handles, in the order they're written.
+fn contour_identifiers<PD: PointData>(contour: &Contour<PD>) -> Vec<&Option<String>> {
+    let mut ret = vec![&contour.identifier];
+    for point in contour.iter() {
+        ret.push(&point.identifier);
+        for (handle, attributes) in [(point.a, &point.a_attributes), (point.b, &point.b_attributes)] {
+            if let Handle::At(..) = handle {
+                ret.push(&attributes.identifier);
+            }
+        }
+    }
+    ret
+}
+
+fn contour_identifiers_mut<PD: PointData>(contour: &mut Contour<PD>) -> Vec<&mut Option<String>> {
+    let mut ret = vec![&mut contour.identifier];
+    for point in contour.points.iter_mut() {
+        ret.push(&mut point.identifier);
+        for (handle, attributes) in [(point.a, &mut point.a_attributes), (point.b, &mut point.b_attributes)] {
+            if let Handle::At(..) = handle {
+                ret.push(&mut attributes.identifier);
+            }
+        }
+    }
+    ret
+}
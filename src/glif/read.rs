@@ -1,20 +1,29 @@
+mod scan;
+pub use self::scan::{GlifHeader, scan_ufo_glif, scan_ufo_glif_from_filename};
 mod xml;
 pub use self::xml::FromXML;
 
+use std::borrow::Cow;
+use std::cell::Cell;
+use std::collections::{HashMap, HashSet};
 use std::convert::TryInto;
+use std::fmt::Display;
+use std::io;
 use std::path;
-use std::rc::Rc;
+use std::sync::Arc;
 
 use integer_or_float::IntegerOrFloat;
+use quick_xml::events::{BytesStart, Event};
 
-use super::Glif;
+use super::{Glif, MAX_FORMAT_MINOR};
+use super::lossless::SourceSpans;
 #[cfg(feature = "glifserde")]
 use super::Lib;
-use crate::error::GlifParserError::{self, GlifInputError};
+use crate::error::{Diagnostic, GlifErrorLocation, GlifParserError::{self, GlifInputError}, LineColumnCursor, Severity};
 use crate::component::GlifComponent;
 use crate::guideline::Guideline;
-use crate::outline::{GlifContour, GlifOutline, Outline};
-use crate::pedantry::Pedantry;
+use crate::outline::{create, GlifContour, Outline};
+use crate::pedantry::{FloatClass, Pedantry};
 use crate::point::{GlifPoint, PointData, PointType};
 use crate::point::IsValid as _;
 use crate::string::GlifStringLenOne;
@@ -28,18 +37,17 @@ macro_rules! input_error {
     }
 }
 
-// Both components and images have the same matrix/identifier values. This is DRY.
-macro_rules! load_matrix_and_identifier {
-    ($xml_el:ident, $struct:ident, ($($attr:ident),+)) => {
+// Both components and images have the same matrix values. This is DRY.
+macro_rules! load_matrix {
+    ($attrs:ident, $struct:ident, ($($attr:ident),+)) => {
         $(
-            let maybe_err = $xml_el.attributes.get(stringify!($attr)).map(|e| -> Result<(),GlifParserError> { 
-                let v = e.as_str().try_into().or(Err(input_error!(concat!("Matrix member ", stringify!($attr), " not float"))))?;
+            let maybe_err = $attrs.get(stringify!($attr)).map(|e| -> Result<(),GlifParserError> {
+                let v = e.try_into().or(Err(input_error!(concat!("Matrix member ", stringify!($attr), " not float"))))?;
                 $struct.$attr = v;
                 Ok(())
             });
             if let Some(Err(e)) = maybe_err { Err(e)?; };
         )+
-        $xml_el.attributes.get("identifier").map(|e|{ $struct.identifier = Some(e.clone()); });
     }
 }
 
@@ -52,17 +60,56 @@ pub fn read_ufo_glif_from_filename<F: AsRef<path::Path> + Clone, PD: PointData>(
 }
 
 pub fn read_ufo_glif_from_filename_pedantic<F: AsRef<path::Path> + Clone, PD: PointData>(filename: F, pedantry: Pedantry) -> Result<Glif<PD>, GlifParserError> {
-    let glifxml = match fs::read_to_string(&filename) {
-        Ok(s) => s,
-        Err(ioe) => Err(GlifParserError::GlifFileIoError(Some(Rc::new(ioe))))?
+    let file = match fs::File::open(&filename) {
+        Ok(f) => f,
+        Err(ioe) => Err(GlifParserError::GlifFileIoError(Some(Arc::new(ioe))).with_filename(filename.as_ref()))?
     };
-    let mut glif: Glif<PD> = read_ufo_glif_pedantic(&glifxml, pedantry)?;
+    read_ufo_glif_from_reader_pedantic(file, Some(filename.as_ref()), pedantry)
+}
+
+/// Read UFO .glif XML from `reader`, e.g. an archive entry or a pipe. If the glif has a known
+/// filename, pass it: it is set as by [`read_ufo_glif_from_filename`], and on any error.
+pub fn read_ufo_glif_from_reader<R: io::Read, PD: PointData>(reader: R, filename: Option<&path::Path>) -> Result<Glif<PD>, GlifParserError> {
+    read_ufo_glif_from_reader_pedantic(reader, filename, Pedantry::default())
+}
+
+pub fn read_ufo_glif_from_reader_pedantic<R: io::Read, PD: PointData>(reader: R, filename: Option<&path::Path>, pedantry: Pedantry) -> Result<Glif<PD>, GlifParserError> {
+    let glif = read_to_string(reader).and_then(|glifxml| read_ufo_glif_pedantic(&glifxml, pedantry));
+    match filename {
+        Some(filename) => {
+            let mut glif = glif.map_err(|e| e.with_filename(filename))?;
+            set_filename(&mut glif, filename.to_path_buf());
+            Ok(glif)
+        },
+        None => glif,
+    }
+}
+
+fn read_to_string<R: io::Read>(mut reader: R) -> Result<String, GlifParserError> {
+    let mut glifxml = vec![];
+    reader.read_to_end(&mut glifxml).map_err(|ioe| GlifParserError::GlifFileIoError(Some(Arc::new(ioe))))?;
+    Ok(String::from_utf8(glifxml)?)
+}
+
+/// As [`read_ufo_glif_with_diagnostics`], but also sets the filename, as
+/// [`read_ufo_glif_from_filename`] does.
+pub fn read_ufo_glif_from_filename_with_diagnostics<F: AsRef<path::Path> + Clone, PD: PointData>(filename: F, pedantry: Pedantry) -> (Option<Glif<PD>>, Vec<Diagnostic>) {
     let filenamepb = filename.as_ref().to_path_buf();
+    let (glif, mut diagnostics) = match fs::read_to_string(&filename) {
+        Ok(glifxml) => read_ufo_glif_with_diagnostics(&glifxml, pedantry),
+        Err(ioe) => (None, vec![Diagnostic::from_error(GlifParserError::GlifFileIoError(Some(Arc::new(ioe))))]),
+    };
+    for diagnostic in diagnostics.iter_mut() {
+        diagnostic.location.filename = Some(filenamepb.clone());
+    }
+    (glif.map(|mut glif| { set_filename(&mut glif, filenamepb); glif }), diagnostics)
+}
+
+fn set_filename<PD: PointData>(glif: &mut Glif<PD>, filename: path::PathBuf) {
     for component in glif.components.vec.iter_mut() {
-        component.set_file_name(&filenamepb);
+        component.set_file_name(&filename);
     }
-    glif.filename = Some(filenamepb);
-    Ok(glif)
+    glif.filename = Some(filename);
 }
 
 /// Read UFO .glif XML to Glif struct. This should only be used when you have no known filename,
@@ -72,246 +119,737 @@ pub fn read_ufo_glif<PD: PointData>(glif: &str) -> Result<Glif<PD>, GlifParserEr
 }
 
 pub fn read_ufo_glif_pedantic<PD: PointData>(glif: &str, pedantry: Pedantry) -> Result<Glif<PD>, GlifParserError> {
-    let mut glif = xmltree::Element::parse(glif.as_bytes())?;
+    read_with_context(&mut ReadContext::new(glif, pedantry, false))
+}
 
-    let mut ret = Glif::new();
+/// As [`read_ufo_glif_pedantic`], also noting where in `glif` each element read lies.
+pub(super) fn read_ufo_glif_with_spans<PD: PointData>(glif: &str, pedantry: Pedantry) -> Result<(Glif<PD>, SourceSpans), GlifParserError> {
+    let mut ctx = ReadContext::new(glif, pedantry, false);
+    ctx.spans = Some(SourceSpans::default());
+    let glif = read_with_context(&mut ctx)?;
+    Ok((glif, ctx.spans.unwrap_or_default()))
+}
 
-    if glif.name != "glyph" {
-        return Err(input_error!("Root element not <glyph>"))
+/// As [`read_ufo_glif_pedantic`], also returning the mends made to the glif as its [`Pedantry`]
+/// allows, e.g. coordinates rounded for TrueType, in document order.
+pub fn read_ufo_glif_with_mends<PD: PointData>(glif: &str, pedantry: Pedantry) -> Result<(Glif<PD>, Vec<Diagnostic>), GlifParserError> {
+    let mut ctx = ReadContext::new(glif, pedantry, false);
+    let glif = read_with_context(&mut ctx)?;
+    Ok((glif, ctx.diagnostics.into_iter().filter(|d| d.severity.is_mend()).collect()))
+}
+
+/// Read UFO .glif XML, recovering from bad elements wherever possible rather than failing on the
+/// first. Returns the glif, unless it was too broken to read at all (e.g. not well-formed XML),
+/// along with every error, mend and warning found, in document order.
+pub fn read_ufo_glif_with_diagnostics<PD: PointData>(glif: &str, pedantry: Pedantry) -> (Option<Glif<PD>>, Vec<Diagnostic>) {
+    let mut ctx = ReadContext::new(glif, pedantry, true);
+    let result = read_with_context(&mut ctx);
+    let mut diagnostics = ctx.diagnostics;
+    match result {
+        Ok(glif) => (Some(glif), diagnostics),
+        Err(e) => {
+            diagnostics.push(Diagnostic::from_error(e));
+            (None, diagnostics)
+        }
+    }
+}
+
+type XmlReader<'a> = quick_xml::Reader<&'a [u8]>;
+
+/// State while reading a `<glyph>`: the element being read, so problems can be located, and the
+/// problems found so far.
+struct ReadContext<'a> {
+    source: &'a str,
+    pedantry: Pedantry,
+    /// Elements from `<glyph>` to the one being read, with their indices among same-named siblings
+    path: Vec<(&'static str, usize)>,
+    /// Byte offsets of where `<glyph>` and each element of `path` start
+    offsets: Vec<usize>,
+    diagnostics: Vec<Diagnostic>,
+    /// Whether to record errors and carry on, rather than fail
+    recover: bool,
+    /// If keeping the source, where its elements lie
+    spans: Option<SourceSpans>,
+    /// Where the last location found was, to find the next from
+    cursor: Cell<LineColumnCursor>,
+    /// The identifiers read so far, which must be unique within the glyph
+    identifiers: HashSet<String>,
+}
+
+impl<'a> ReadContext<'a> {
+    fn new(source: &'a str, pedantry: Pedantry, recover: bool) -> Self {
+        Self { source, pedantry, path: vec![], offsets: vec![], diagnostics: vec![], recover, spans: None, cursor: Cell::default(), identifiers: HashSet::new() }
     }
 
-    if glif.attributes.get("format").ok_or(input_error!("no format in <glyph>"))? != "2" {
-        return Err(input_error!("<glyph> format not 2"))
+    fn enter(&mut self, name: &'static str, idx: usize, offset: usize) {
+        self.path.push((name, idx));
+        self.offsets.push(offset);
     }
 
-    ret.name = glif
-        .attributes
-        .get("name")
-        .ok_or(input_error!("<glyph> has no name"))?
-        .clone();
-    ret.components.root = ret.name.clone();
+    fn leave(&mut self) {
+        self.path.pop();
+        self.offsets.pop();
+    }
+
+    fn line_column(&self, offset: usize) -> (u64, u64) {
+        let mut cursor = self.cursor.get();
+        let line_column = cursor.seek(self.source, offset);
+        self.cursor.set(cursor);
+        line_column
+    }
 
-    let advance = glif
-        .take_child("advance");
+    fn location(&self) -> GlifErrorLocation {
+        GlifErrorLocation::new(self.line_column(self.offsets.last().copied().unwrap_or(0)), &self.path)
+    }
 
-    ret.width = if let Some(a) = advance {
-        let width = a.attributes
-            .get("width")
-            .ok_or(input_error!("<advance> has no width"))?;
-        let widthc = width.parse::<u64>();
+    /// An XML syntax error, located where the reader stopped rather than at the element.
+    fn xml_error(&self, reader: &XmlReader, e: impl Display) -> GlifParserError {
+        let location = GlifErrorLocation::new(self.line_column(reader.buffer_position()), &self.path);
+        GlifParserError::XmlParseError(e.to_string()).located(location)
+    }
 
-        match widthc {
-            Err(e) => if let Ok((f, false)) = width.parse::<f32>().map(|f|(f, f.is_subnormal())) {
-                const FLOATPOINTWARNING: &str = "Floating point value given as <advance> width — OpenType `hmtx` / `vmtx` will truncate it";
-                if !pedantry.should_mend() {
-                    Err(input_error!(FLOATPOINTWARNING))?
-                }
-                log::warn!("{}, so we do too!", FLOATPOINTWARNING);
-                Some(f as u64)
-            } else {
-                log::trace!("<advance> parsing as int rose {:?}", e);
-                Err(input_error!("<advance> width neither int nor downgradable (not subnormal) float!"))?
-            },
-            Ok(i) => Some(i)
+    /// The next event inside the element being read, and the offset it starts at.
+    fn next(&self, reader: &mut XmlReader<'a>) -> Result<(usize, Event<'a>), GlifParserError> {
+        let offset = reader.buffer_position();
+        match reader.read_event() {
+            Ok(Event::Eof) => Err(self.xml_error(reader, "Unexpected end of file inside an element")),
+            Ok(event) => Ok((offset, event)),
+            Err(e) => Err(self.xml_error(reader, e)),
         }
-    } else {
-        None
-    };
+    }
 
-    let mut unicodes = vec![];
-    while let Some(u) = glif.take_child("unicode") {
-        let unicodehex = u
-            .attributes
-            .get("hex")
-            .ok_or(input_error!("<unicode> has no hex"))?;
-        unicodes.push(
-            char::from_u32(
-                u32::from_str_radix(unicodehex, 16)
-                .or(Err(input_error!("<unicode> hex not int")))?
-            )
-            .ok_or(input_error!("<unicode> char conversion failed"))?,
-        );
-    }
-
-    ret.unicode = unicodes;
-
-    let mut anchors: Vec<Anchor<PD>> = Vec::new();
-
-    while let Some(anchor_el) = glif.take_child("anchor") {
-        let mut anchor = GlifAnchor::default();
-
-        anchor.x = anchor_el
-            .attributes
-            .get("x")
-            .ok_or(input_error!("<anchor> missing x"))?
-            .parse()
-            .or(Err(input_error!("<anchor> x not integer/float")))?;
-        anchor.y = anchor_el
-            .attributes
-            .get("y")
-            .ok_or(input_error!("<anchor> missing y"))?
-            .parse()
-            .or(Err(input_error!("<anchor> y not integer/float")))?;
-        anchor.class = anchor_el
-            .attributes
-            .get("name")
-            .map(|a|GlifStringLenOne::try_from(a.clone()))
-            .map_or(None, |r|r.ok());
-        anchors.push(Anchor::from_glif(&anchor, pedantry)?);
-    }
-
-    ret.anchors = anchors;
-
-    #[cfg(feature = "glifimage")] {
-    let mut images: Vec<GlifImage> = Vec::new();
-
-    while let Some(image_el) = glif.take_child("image") {
-        let filename = path::PathBuf::from(image_el
-            .attributes
-            .get("fileName")
-            .ok_or(input_error!("<image> missing fileName"))?);
-
-        let mut gimage = GlifImage::from_filename(filename)?;
-
-        load_matrix_and_identifier!(image_el, gimage, (xScale, xyScale, yxScale, yScale, xOffset, yOffset));
-
-        if let Some(color) = image_el.attributes.get("color") {
-            gimage.color = Some(color.parse()?);
+    /// Skip the rest of a non-empty element, e.g. one we don't know.
+    fn skip(&self, reader: &mut XmlReader<'a>, start: &BytesStart) -> Result<(), GlifParserError> {
+        match reader.read_to_end(start.name()) {
+            Ok(_) => Ok(()),
+            Err(e) => Err(self.xml_error(reader, e)),
         }
+    }
 
-        images.push(gimage);
+    fn attributes<'b>(&self, start: &'b BytesStart) -> Result<Attributes<'b>, GlifParserError> {
+        Attributes::of(start).map_err(|e| e.located(self.location()))
     }
 
-    ret.images = images;
+    /// Fail with `error`, or, if recovering, record it so the caller can carry on.
+    fn error(&mut self, error: GlifParserError) -> Result<(), GlifParserError> {
+        if !self.recover {
+            return Err(error)
+        }
+        let error = error.located(self.location());
+        self.diagnostics.push(Diagnostic::from_error(error));
+        Ok(())
     }
 
-    let mut guidelines: Vec<Guideline<PD>> = Vec::new();
+    /// As [`Self::error`], with `Ok(None)` meaning the caller should skip what it was reading.
+    fn recover<T>(&mut self, result: Result<T, GlifParserError>) -> Result<Option<T>, GlifParserError> {
+        match result {
+            Ok(t) => Ok(Some(t)),
+            Err(e) => self.error(e).map(|()| None),
+        }
+    }
 
-    while let Some(guideline_el) = glif.take_child("guideline") {
-        let gx = guideline_el
-            .attributes
-            .get("x")
-            .ok_or(input_error!("<guideline> missing x"))?
-            .parse()
-            .or(Err(input_error!("<guideline> x not float")))?;
-        let gy = guideline_el
-            .attributes
-            .get("y")
-            .ok_or(input_error!("<guideline> missing y"))?
-            .parse()
-            .or(Err(input_error!("<guideline> x not float")))?;
-        let angle: IntegerOrFloat = guideline_el
-            .attributes
-            .get("angle")
-            .ok_or(input_error!("<guideline> missing angle"))?
-            .as_str()
-            .try_into()
-            .or(Err(input_error!("<guideline> angle not float")))?;
+    fn note(&mut self, severity: Severity, message: String) {
+        log::warn!("{}", message);
+        let location = self.location();
+        self.diagnostics.push(Diagnostic { severity, message, location });
+    }
 
-        let mut guideline = Guideline::from_x_y_angle(gx, gy, angle);
+    /// `value`, which must be an integer if the pedantry level requires one of class `fc`, rounded
+    /// if the pedantry allows mending it to be one.
+    fn integer(&mut self, value: IntegerOrFloat, fc: FloatClass, what: &str) -> Result<IntegerOrFloat, GlifParserError> {
+        let f = f32::from(value);
+        if f.fract() == 0. || !self.pedantry.level.requires_integer(fc) {
+            return Ok(value)
+        }
+        if !self.pedantry.mend.meets_level() {
+            return Err(GlifInputError(format!("{} {} not an integer, as {:?} pedantry requires", what, value, self.pedantry.level)))
+        }
+        self.mend(format!("Rounded {} {} to {}", what, value, f.round()));
+        Ok(IntegerOrFloat::Integer(f.round() as _))
+    }
 
-        if let Some(color) = guideline_el.attributes.get("color") {
-            guideline.color = Some(color.parse()?);
+    /// The element's identifier. Identifiers must be unique within a glyph, and of at most 100
+    /// characters in the range 0x20 to 0x7E. Bad ones are tolerated at
+    /// [`Level::GlifParser`](crate::pedantry::Level::GlifParser), removed at stricter levels if
+    /// we may fix spec errors, and rejected otherwise.
+    fn identifier(&mut self, attrs: &Attributes) -> Result<Option<String>, GlifParserError> {
+        let Some(id) = attrs.get("identifier") else { return Ok(None) };
+        let problem = if id.len() > 100 || !id.bytes().all(|b| (0x20..=0x7E).contains(&b)) {
+            "Invalid"
+        } else if !self.identifiers.insert(id.to_string()) {
+            "Duplicate"
+        } else {
+            return Ok(Some(id.to_string()))
+        };
+        if self.pedantry.level.is_glif_parser() {
+            self.warn(format!("{} identifier {:?}", problem, id));
+        } else if self.pedantry.mend.fixes_errors() {
+            self.mend(format!("Removed {} identifier {:?}", problem.to_lowercase(), id));
+            return Ok(None)
+        } else {
+            self.error(input_error!(format!("{} identifier {:?}", problem, id)))?;
         }
+        Ok(Some(id.to_string()))
+    }
+
+    fn mend(&mut self, message: impl Into<String>) {
+        self.note(Severity::Mend, message.into())
+    }
+
+    fn warn(&mut self, message: impl Into<String>) {
+        self.note(Severity::Warning, message.into())
+    }
+}
 
-        guideline.name = guideline_el.attributes.get("name").map(|n|n.clone());
+/// An element's attributes, unescaped and keyed by local name.
+struct Attributes<'a>(Vec<(&'a [u8], Cow<'a, str>)>);
 
-        guideline.identifier = guideline_el.attributes.get("identifier").map(|i|i.clone());
+impl<'a> Attributes<'a> {
+    fn of(start: &'a BytesStart) -> Result<Self, GlifParserError> {
+        start.attributes()
+            .map(|a| { let a = a?; Ok((a.key.local_name().into_inner(), a.unescape_value()?)) })
+            .collect::<Result<_, GlifParserError>>()
+            .map(Self)
+    }
 
-        guidelines.push(guideline);
+    fn get(&self, name: &str) -> Option<&str> {
+        self.0.iter().find(|(k, _)| *k == name.as_bytes()).map(|(_, v)| v.as_ref())
     }
+}
 
-    ret.guidelines = guidelines;
+fn is_xml_whitespace(text: &[u8]) -> bool {
+    text.iter().all(|b| b" \t\r\n".contains(b))
+}
 
-    if let Some(note_el) = glif.take_child("note") {
-        note_el.get_text().map(|t|ret.note=Some(t.into_owned()));
+/// A start or empty element event, and whether it was empty; `None` for any other event.
+fn element<'a>(event: Event<'a>) -> Option<(BytesStart<'a>, bool)> {
+    match event {
+        Event::Start(start) => Some((start, false)),
+        Event::Empty(start) => Some((start, true)),
+        _ => None,
     }
+}
 
-    let mut goutline: GlifOutline = GlifOutline::new();
+fn read_with_context<PD: PointData>(ctx: &mut ReadContext) -> Result<Glif<PD>, GlifParserError> {
+    let mut reader = XmlReader::from_str(ctx.source);
+    read_glyph(&mut reader, ctx).map_err(|e| e.located(ctx.location()))
+}
 
-    let outline_el = glif.take_child("outline");
+/// Read up to the `<glyph>` start tag, returning it and whether it's an empty element.
+fn read_root<'a>(reader: &mut XmlReader<'a>, ctx: &mut ReadContext<'a>) -> Result<(BytesStart<'a>, bool), GlifParserError> {
+    let (glif, empty) = loop {
+        let offset = reader.buffer_position();
+        match reader.read_event().map_err(|e| ctx.xml_error(reader, e))? {
+            Event::Start(start) => { ctx.offsets.push(offset); break (start, false) },
+            Event::Empty(start) => { ctx.offsets.push(offset); break (start, true) },
+            Event::Text(t) if !is_xml_whitespace(&t) => return Err(ctx.xml_error(reader, "Text outside of the root element")),
+            Event::Eof => return Err(ctx.xml_error(reader, "No root element")),
+            _ => {},
+        }
+    };
 
-    if let Some(mut outline_elu) = outline_el {
-        while let Some(mut contour_el) = outline_elu.take_child("contour") {
-            let mut gcontour: GlifContour = Vec::new();
-            while let Some(point_el) = contour_el.take_child("point") {
-                let mut gpoint = GlifPoint::new();
+    if glif.local_name().as_ref() != b"glyph" {
+        return Err(input_error!("Root element not <glyph>"))
+    }
 
-                gpoint.x = point_el
-                    .attributes
-                    .get("x")
-                    .ok_or(input_error!("<point> missing x"))?
-                    .parse()
-                    .or(Err(input_error!("<point> x not float")))?;
-                gpoint.y = point_el
-                    .attributes
-                    .get("y")
-                    .ok_or(input_error!("<point> missing y"))?
-                    .parse()
-                    .or(Err(input_error!("<point> y not float")))?;
+    Ok((glif, empty))
+}
 
-                match point_el.attributes.get("name") {
-                    Some(n) => gpoint.name = Some(n.clone()),
-                    None => {}
-                }
+/// Check that only whitespace, comments and processing instructions follow `</glyph>`, as XML
+/// allows a single root element.
+fn read_trailing<'a>(reader: &mut XmlReader<'a>, ctx: &mut ReadContext<'a>) -> Result<(), GlifParserError> {
+    loop {
+        let error = match reader.read_event() {
+            Ok(Event::Eof) => return Ok(()),
+            Ok(Event::Text(t)) if is_xml_whitespace(&t) => continue,
+            Ok(Event::Comment(_) | Event::PI(_)) => continue,
+            Ok(_) => ctx.xml_error(reader, "Content after the root element"),
+            Err(e) => ctx.xml_error(reader, e),
+        };
+        return ctx.error(error)
+    }
+}
 
-                gpoint.ptype = point_el.attributes.get("type").as_ref().map(|s| s.as_str()).unwrap_or("offcurve").into();
+/// Elements read from `<glyph>`, as named in `ctx.path`. Others are skipped.
+const GLYPH_CHILDREN: [&str; 8] = ["advance", "unicode", "anchor", "image", "guideline", "note", "outline", "lib"];
 
-                match point_el.attributes.get("smooth") {
-                    Some(s) => if s == "yes" {
-                        if gpoint.ptype != PointType::OffCurve {
-                            gpoint.smooth = true;
-                        } else {
-                            log::error!("Ignoring illogical `smooth=yes` on offcurve point");
-                        }
-                    },
-                    _ => {}
-                }
+/// Read `<glyph>` in a single pass over the XML, keeping `ctx.path` pointed at the element being
+/// read. Of `<advance>`, `<note>`, `<outline>` and `<lib>`, only the first is read.
+fn read_glyph<'a, PD: PointData>(reader: &mut XmlReader<'a>, ctx: &mut ReadContext<'a>) -> Result<Glif<PD>, GlifParserError> {
+    let mut ret = Glif::new();
 
-                if gpoint.ptype.is_valid() {
-                    gcontour.push(gpoint);
-                } else {
-                    Err(GlifInputError(format!("Shouldn't write <point type={}> to UFO .glif!", gpoint.ptype)))?;
-                }
+    let (glif, empty) = read_root(reader, ctx)?;
+    let glif_attrs = ctx.attributes(&glif)?;
+    if let Some(spans) = ctx.spans.as_mut() {
+        spans.glyph = ctx.offsets[0]..reader.buffer_position();
+        spans.glyph_empty = empty;
+    }
+
+    let format_1 = match glif_attrs.get("format") {
+        Some("2") => false,
+        Some("1") => {
+            if ctx.pedantry.mend.upgrades_outdated() {
+                ctx.mend("Upgrading GLIF format 1 glyph to format 2");
+            } else {
+                ctx.error(input_error!("<glyph> format 1 (UFO2) and pedantry forbids upgrading it"))?;
             }
-            if gcontour.len() > 0 {
-                goutline.push(gcontour);
+            true
+        },
+        Some(_) => { ctx.error(input_error!("<glyph> format neither 1 nor 2"))?; false },
+        None => { ctx.error(input_error!("no format in <glyph>"))?; false },
+    };
+    if let Some(spans) = ctx.spans.as_mut() {
+        spans.format_1 = format_1;
+    }
+
+    if let Some(format_minor) = glif_attrs.get("formatMinor") {
+        match format_minor.parse() {
+            Ok(format_minor) => ret.format_minor = format_minor,
+            Err(_) => ctx.error(input_error!("<glyph> formatMinor not a non-negative integer"))?,
+        }
+        if format_1 && ret.format_minor != 0 {
+            ctx.error(input_error!("<glyph> formatMinor given for format 1, which has no minor versions"))?;
+        }
+        if ret.format_minor > MAX_FORMAT_MINOR {
+            if ctx.pedantry.level.is_glif_parser() {
+                ctx.warn(format!("Reading GLIF format 2.{} as if it were 2.{}; data new to 2.{} may be lost", ret.format_minor, MAX_FORMAT_MINOR, ret.format_minor));
+            } else {
+                ctx.error(GlifInputError(format!("<glyph> formatMinor {} is newer than the newest known (2.{})", ret.format_minor, MAX_FORMAT_MINOR)))?;
             }
         }
-        
-        while let Some(component_el) = outline_elu.take_child("component") {
-            let mut gcomponent = GlifComponent::new();
-            load_matrix_and_identifier!(component_el, gcomponent, (xScale, xyScale, yxScale, yScale, xOffset, yOffset));
-            gcomponent.base = component_el.attributes.get("base").ok_or(input_error!("<component> missing base"))?.clone();
-            ret.components.vec.push(gcomponent);
+    }
+
+    match glif_attrs.get("name") {
+        Some(name) => ret.name = name.to_string(),
+        None => ctx.error(input_error!("<glyph> has no name"))?,
+    }
+    ret.components.root = ret.name.clone();
+
+    let mut outline: Outline<PD> = vec![];
+    // Format 1 anchors, which come after any `<anchor>` elements
+    let mut contour_anchors = vec![];
+    let mut counts: HashMap<&'static str, usize> = HashMap::new();
+
+    loop {
+        if empty {
+            break;
         }
+        let (offset, event) = ctx.next(reader)?;
+        if let Event::End(_) = event {
+            break;
+        }
+        let Some((el, el_empty)) = element(event) else { continue };
+        let Some(name) = GLYPH_CHILDREN.iter().copied().find(|n| n.as_bytes() == el.local_name().as_ref()) else {
+            if !el_empty { ctx.skip(reader, &el)?; }
+            continue;
+        };
+        let count = counts.entry(name).or_insert(0);
+        let idx = *count;
+        *count += 1;
+        ctx.enter(name, idx, offset);
+        let mut content_read = false;
+        // The index of what a list element became in its `Vec`, if anything
+        let mut read_idx = None;
+
+        if format_1 && idx == 0 && ["anchor", "guideline", "image"].contains(&name) && !ctx.pedantry.level.is_glif_parser() {
+            ctx.error(GlifInputError(format!("<{}> is not allowed in GLIF format 1", name)))?;
+        }
+
+        match name {
+            "advance" if idx == 0 => {
+                let attrs = ctx.attributes(&el)?;
+                let advance = read_advance(&attrs, ctx);
+                if let Some((width, height)) = ctx.recover(advance)? {
+                    ret.width = width;
+                    ret.height = height;
+                }
+            },
+            "unicode" => {
+                let attrs = ctx.attributes(&el)?;
+                if let Some(c) = ctx.recover(read_unicode(&attrs))? {
+                    ret.unicode.push(c);
+                    read_idx = Some(ret.unicode.len() - 1);
+                }
+            },
+            "anchor" => {
+                let attrs = ctx.attributes(&el)?;
+                let anchor = read_anchor(&attrs, ctx);
+                if let Some(anchor) = ctx.recover(anchor)? {
+                    ret.anchors.push(anchor);
+                    read_idx = Some(ret.anchors.len() - 1);
+                }
+            },
+            #[cfg(feature = "glifimage")]
+            "image" => {
+                let attrs = ctx.attributes(&el)?;
+                let image = read_image(&attrs, ctx);
+                if let Some(image) = ctx.recover(image)? {
+                    ret.images.push(image);
+                    read_idx = Some(ret.images.len() - 1);
+                }
+            },
+            "guideline" => {
+                let attrs = ctx.attributes(&el)?;
+                let guideline = read_guideline(&attrs, ctx);
+                if let Some(guideline) = ctx.recover(guideline)? {
+                    ret.guidelines.push(guideline);
+                    read_idx = Some(ret.guidelines.len() - 1);
+                }
+            },
+            "note" if idx == 0 && !el_empty => {
+                ret.note = read_text(reader, ctx)?;
+                content_read = true;
+            },
+            "outline" if idx == 0 && !el_empty => {
+                read_outline(reader, ctx, format_1, &mut outline, &mut ret.components.vec, &mut contour_anchors)?;
+                content_read = true;
+            },
+            #[cfg(feature = "glifserde")]
+            "lib" if idx == 0 => {
+                if let Some(lib) = read_lib(reader, ctx, &el, el_empty)? {
+                    ret.lib = lib;
+                }
+                content_read = true;
+            },
+            #[cfg(not(feature = "glifserde"))]
+            "lib" if idx == 0 => ctx.warn("Without glifserde, cannot decode plist!"),
+            _ => {},
+        }
+
+        if !el_empty && !content_read {
+            ctx.skip(reader, &el)?;
+        }
+        let was_read = idx == 0 || !matches!(name, "advance" | "note" | "outline" | "lib");
+        if let (Some(spans), true) = (ctx.spans.as_mut(), was_read) {
+            spans.children.push((name, read_idx, offset..reader.buffer_position()));
+        }
+        ctx.leave();
+    }
+    read_trailing(reader, ctx)?;
+
+    ret.anchors.extend(contour_anchors);
+
+    if !outline.is_empty() || !ret.components.vec.is_empty() {
+        ret.outline = Some(outline);
     }
 
     #[cfg(feature = "glifserde")]
-    if let Some(mut lib) = glif.take_child("lib") {
-        let mut plist_temp: Vec<u8> = vec![];
-        lib.name = String::from("plist");
-        match lib.write(&mut plist_temp).map(|()|plist::from_bytes(&plist_temp)) {
-            Ok(Ok(lib_p)) => ret.lib = Lib::Plist(lib_p),
-            Err(e) => {
-                log::error!("Failed to serialize .glif lib as XML? Error: {:?}", e);
-                ret.lib = Lib::Xml(lib)
+    for warning in ret.take_object_libs() {
+        ctx.warn(warning);
+    }
+
+    Ok(ret)
+}
+
+/// The text of the element being read, ignoring any child elements, and whitespace-only runs
+/// unless they follow CDATA (as xmltree did).
+fn read_text<'a>(reader: &mut XmlReader<'a>, ctx: &ReadContext<'a>) -> Result<Option<String>, GlifParserError> {
+    let mut text: Option<String> = None;
+    let mut after_cdata = false;
+    loop {
+        let event = ctx.next(reader)?.1;
+        match &event {
+            Event::Text(t) if after_cdata || !is_xml_whitespace(t) => {
+                let t = t.unescape().map_err(|e| ctx.xml_error(reader, e))?;
+                text.get_or_insert_with(String::new).push_str(&t);
+            },
+            Event::CData(c) => text.get_or_insert_with(String::new).push_str(&String::from_utf8_lossy(c)),
+            Event::Start(start) => ctx.skip(reader, start)?,
+            Event::End(_) => return Ok(text),
+            _ => {},
+        }
+        after_cdata = matches!(event, Event::CData(_));
+    }
+}
+
+/// Read the contours and components of `<outline>`, in document order.
+fn read_outline<'a, PD: PointData>(reader: &mut XmlReader<'a>, ctx: &mut ReadContext<'a>, format_1: bool, outline: &mut Outline<PD>, components: &mut Vec<GlifComponent>, contour_anchors: &mut Vec<Anchor<PD>>) -> Result<(), GlifParserError> {
+    let (mut contours, mut component_count) = (0, 0);
+    loop {
+        let (offset, event) = ctx.next(reader)?;
+        if let Event::End(_) = event {
+            return Ok(());
+        }
+        let Some((el, el_empty)) = element(event) else { continue };
+        // The index of what the element became in `outline` or `components`, if anything
+        let mut read_idx = None;
+        match el.local_name().as_ref() {
+            b"contour" => {
+                ctx.enter("contour", contours, offset);
+                contours += 1;
+                let attrs = ctx.attributes(&el)?;
+                let mut gcontour = GlifContour::new().identifier(ctx.identifier(&attrs)?);
+                if !el_empty {
+                    read_contour(reader, ctx, &mut gcontour)?;
+                }
+                // GLIF format 1 stores anchors as contours of a single named `move` point.
+                if format_1 && gcontour.len() == 1 && gcontour[0].ptype == PointType::Move && gcontour[0].name.is_some() {
+                    let gpoint = gcontour.pop().unwrap();
+                    let anchor = format_1_anchor(gpoint, ctx);
+                    if let Some(anchor) = ctx.recover(anchor)? {
+                        contour_anchors.push(anchor);
+                    }
+                } else if let Some(contour) = ctx.recover(create::contour(&gcontour))? {
+                    if !contour.is_empty() {
+                        outline.push(contour);
+                        read_idx = Some(outline.len() - 1);
+                    }
+                }
+                if let Some(spans) = ctx.spans.as_mut() {
+                    spans.outline_children.push(("contour", read_idx, offset..reader.buffer_position()));
+                }
+                ctx.leave();
+            },
+            b"component" => {
+                ctx.enter("component", component_count, offset);
+                component_count += 1;
+                let attrs = ctx.attributes(&el)?;
+                let gcomponent = read_component(&attrs, ctx);
+                if let Some(gcomponent) = ctx.recover(gcomponent)? {
+                    components.push(gcomponent);
+                    read_idx = Some(components.len() - 1);
+                }
+                if !el_empty {
+                    ctx.skip(reader, &el)?;
+                }
+                if let Some(spans) = ctx.spans.as_mut() {
+                    spans.outline_children.push(("component", read_idx, offset..reader.buffer_position()));
+                }
+                ctx.leave();
             },
-            Ok(Err(e)) => {
-                log::error!("Failed to deserialize .glif lib XML as plist? Error: {:?}", e);
+            _ => if !el_empty {
+                ctx.skip(reader, &el)?;
+            },
+        }
+    }
+}
+
+fn read_contour<'a>(reader: &mut XmlReader<'a>, ctx: &mut ReadContext<'a>, gcontour: &mut GlifContour) -> Result<(), GlifParserError> {
+    let mut points = 0;
+    loop {
+        let (offset, event) = ctx.next(reader)?;
+        if let Event::End(_) = event {
+            return Ok(());
+        }
+        let Some((el, el_empty)) = element(event) else { continue };
+        if el.local_name().as_ref() != b"point" {
+            if !el_empty {
+                ctx.skip(reader, &el)?;
             }
+            continue;
         }
+        ctx.enter("point", points, offset);
+        points += 1;
+        let attrs = ctx.attributes(&el)?;
+        let gpoint = read_point(&attrs, ctx);
+        if let Some(gpoint) = ctx.recover(gpoint)? {
+            gcontour.push(gpoint);
+        }
+        if !el_empty {
+            ctx.skip(reader, &el)?;
+        }
+        ctx.leave();
     }
-    #[cfg(not(feature = "glifserde"))]
-    if let Some(_) = glif.take_child("lib") {
-        log::warn!("Without glifserde, cannot decode plist!")
+}
+
+/// Read `<lib>` by handing its content, as a `<plist>`, to the plist crate.
+#[cfg(feature = "glifserde")]
+fn read_lib<'a>(reader: &mut XmlReader<'a>, ctx: &mut ReadContext<'a>, lib: &BytesStart, empty: bool) -> Result<Option<Lib>, GlifParserError> {
+    let content = if empty {
+        ""
+    } else {
+        let span = reader.read_to_end(lib.name()).map_err(|e| ctx.xml_error(reader, e))?;
+        &ctx.source[span]
+    };
+    match plist::from_bytes(format!("<plist>{}</plist>", content).as_bytes()) {
+        Ok(lib_p) => {
+            let mut lib = Lib::Plist(lib_p);
+            if !ctx.pedantry.level.is_glif_parser() {
+                if let Err(e) = lib.validate() {
+                    if !ctx.pedantry.mend.fixes_errors() {
+                        ctx.error(e)?;
+                    } else {
+                        for key in lib.remove_invalid() {
+                            ctx.mend(format!("Removed {} from <lib>, as its value breaks the UFO spec", key));
+                        }
+                    }
+                }
+            }
+            Ok(Some(lib))
+        },
+        Err(e) => {
+            ctx.warn(format!("Failed to deserialize .glif lib XML as plist? Error: {:?}", e));
+            Ok(None)
+        }
     }
+}
+
+fn read_advance(advance: &Attributes, ctx: &mut ReadContext) -> Result<(Option<u64>, Option<u64>), GlifParserError> {
+    let width = parse_advance(advance, "width", ctx)?;
+    let height = parse_advance(advance, "height", ctx)?;
+    if width.is_none() && height.is_none() {
+        Err(input_error!("<advance> has neither width nor height"))?
+    }
+    Ok((width, height))
+}
 
-    let outline: Outline<PD> = goutline.into();
+fn parse_advance(advance: &Attributes, attr: &str, ctx: &mut ReadContext) -> Result<Option<u64>, GlifParserError> {
+    let Some(value) = advance.get(attr) else { return Ok(None) };
 
-    if outline.len() > 0 || ret.components.vec.len() > 0 {
-        ret.outline = Some(outline);
+    match value.parse::<u64>() {
+        Err(e) => if let Ok((f, false)) = value.parse::<f32>().map(|f|(f, f.is_subnormal())) {
+            if !f.is_finite() || f < 0. {
+                Err(input_error!(format!("<advance> {} {} is not a non-negative number", attr, value)))?
+            }
+            let floatpointwarning = format!("Floating point value given as <advance> {} — OpenType `hmtx` / `vmtx` will truncate it", attr);
+            if !ctx.pedantry.allows(IntegerOrFloat::Float(f), FloatClass::AdvanceWidth) || (f.fract() != 0. && ctx.pedantry.mend.is_never()) {
+                Err(input_error!(floatpointwarning))?
+            }
+            ctx.mend(format!("{}, so we do too!", floatpointwarning));
+            Ok(Some(f as u64))
+        } else {
+            log::trace!("<advance> parsing as int rose {:?}", e);
+            Err(input_error!(format!("<advance> {} neither int nor downgradable (not subnormal) float!", attr)))
+        },
+        Ok(i) => Ok(Some(i))
     }
+}
 
-    Ok(ret)
+fn read_unicode(u: &Attributes) -> Result<char, GlifParserError> {
+    let unicodehex = u
+        .get("hex")
+        .ok_or(input_error!("<unicode> has no hex"))?;
+    char::from_u32(
+        u32::from_str_radix(unicodehex, 16)
+        .or(Err(input_error!("<unicode> hex not int")))?
+    )
+    .ok_or(input_error!("<unicode> char conversion failed"))
 }
+
+fn read_anchor<PD: PointData>(anchor_el: &Attributes, ctx: &mut ReadContext) -> Result<Anchor<PD>, GlifParserError> {
+    let x = anchor_el
+        .get("x")
+        .ok_or(input_error!("<anchor> missing x"))?
+        .parse()
+        .or(Err(input_error!("<anchor> x not integer/float")))?;
+    let y = anchor_el
+        .get("y")
+        .ok_or(input_error!("<anchor> missing y"))?
+        .parse()
+        .or(Err(input_error!("<anchor> y not integer/float")))?;
+    let x = ctx.integer(x, FloatClass::Anchor, "<anchor> x")?;
+    let y = ctx.integer(y, FloatClass::Anchor, "<anchor> y")?;
+    let class = anchor_el
+        .get("name")
+        .and_then(|a|GlifStringLenOne::try_from(a.to_string()).ok());
+    let color = anchor_el.get("color").map(|c|c.parse()).transpose()?;
+    let identifier = ctx.identifier(anchor_el)?;
+
+    Ok(Anchor::from(&GlifAnchor { x, y, class, color, identifier }))
+}
+
+/// GLIF format 1 stores anchors as contours of a single named `move` point.
+fn format_1_anchor<PD: PointData>(gpoint: GlifPoint, ctx: &mut ReadContext) -> Result<Anchor<PD>, GlifParserError> {
+    let x = ctx.integer(gpoint.x, FloatClass::Anchor, "anchor x")?;
+    let y = ctx.integer(gpoint.y, FloatClass::Anchor, "anchor y")?;
+    let class = gpoint.name.and_then(|n|GlifStringLenOne::try_from(n).ok());
+    Ok(Anchor::from(&GlifAnchor::new(x, y, class)))
+}
+
+#[cfg(feature = "glifimage")]
+fn read_image(image_el: &Attributes, ctx: &mut ReadContext) -> Result<GlifImage, GlifParserError> {
+    let filename = path::PathBuf::from(image_el
+        .get("fileName")
+        .ok_or(input_error!("<image> missing fileName"))?);
+
+    let mut gimage = GlifImage::from_filename(filename)?;
+
+    load_matrix!(image_el, gimage, (xScale, xyScale, yxScale, yScale, xOffset, yOffset));
+    gimage.identifier = ctx.identifier(image_el)?;
+
+    if let Some(color) = image_el.get("color") {
+        gimage.color = Some(color.parse()?);
+    }
+
+    Ok(gimage)
+}
+
+fn read_guideline<PD: PointData>(guideline_el: &Attributes, ctx: &mut ReadContext) -> Result<Guideline<PD>, GlifParserError> {
+    let gx = guideline_el
+        .get("x")
+        .ok_or(input_error!("<guideline> missing x"))?
+        .parse()
+        .or(Err(input_error!("<guideline> x not float")))?;
+    let gy = guideline_el
+        .get("y")
+        .ok_or(input_error!("<guideline> missing y"))?
+        .parse()
+        .or(Err(input_error!("<guideline> y not float")))?;
+    let gx = ctx.integer(gx, FloatClass::Guideline, "<guideline> x")?.into();
+    let gy = ctx.integer(gy, FloatClass::Guideline, "<guideline> y")?.into();
+    let angle: IntegerOrFloat = guideline_el
+        .get("angle")
+        .ok_or(input_error!("<guideline> missing angle"))?
+        .try_into()
+        .or(Err(input_error!("<guideline> angle not float")))?;
+
+    let mut guideline = Guideline::from_x_y_angle(gx, gy, angle);
+
+    if let Some(color) = guideline_el.get("color") {
+        guideline.color = Some(color.parse()?);
+    }
+
+    guideline.name = guideline_el.get("name").map(String::from);
+
+    guideline.identifier = ctx.identifier(guideline_el)?;
+
+    Ok(guideline)
+}
+
+fn read_point(point_el: &Attributes, ctx: &mut ReadContext) -> Result<GlifPoint, GlifParserError> {
+    let mut gpoint = GlifPoint::new();
+
+    gpoint.x = point_el
+        .get("x")
+        .ok_or(input_error!("<point> missing x"))?
+        .parse()
+        .or(Err(input_error!("<point> x not float")))?;
+    gpoint.y = point_el
+        .get("y")
+        .ok_or(input_error!("<point> missing y"))?
+        .parse()
+        .or(Err(input_error!("<point> y not float")))?;
+    gpoint.x = ctx.integer(gpoint.x, FloatClass::Point, "<point> x")?;
+    gpoint.y = ctx.integer(gpoint.y, FloatClass::Point, "<point> y")?;
+
+    gpoint.name = point_el.get("name").map(String::from);
+
+    gpoint.identifier = ctx.identifier(point_el)?;
+
+    gpoint.ptype = point_el.get("type").unwrap_or("offcurve").into();
+
+    if point_el.get("smooth").map(|s| s == "yes").unwrap_or(false) {
+        if gpoint.ptype != PointType::OffCurve {
+            gpoint.smooth = true;
+        } else {
+            ctx.mend("Ignoring illogical `smooth=yes` on offcurve point");
+        }
+    }
+
+    if !gpoint.ptype.is_valid() {
+        Err(GlifInputError(format!("Shouldn't write <point type={}> to UFO .glif!", gpoint.ptype)))?;
+    }
+
+    Ok(gpoint)
+}
+
+fn read_component(component_el: &Attributes, ctx: &mut ReadContext) -> Result<GlifComponent, GlifParserError> {
+    let mut gcomponent = GlifComponent::new();
+    load_matrix!(component_el, gcomponent, (xScale, xyScale, yxScale, yScale, xOffset, yOffset));
+    gcomponent.identifier = ctx.identifier(component_el)?;
+    gcomponent.xOffset = ctx.integer(gcomponent.xOffset, FloatClass::ComponentOffset, "<component> xOffset")?;
+    gcomponent.yOffset = ctx.integer(gcomponent.yOffset, FloatClass::ComponentOffset, "<component> yOffset")?;
+    gcomponent.base = component_el.get("base").ok_or(input_error!("<component> missing base"))?.to_string();
+    Ok(gcomponent)
+}
+
@@ -0,0 +1,112 @@
+//! Reading only what's needed to index a .glif: its name, unicodes, advance and component bases.
+
+use std::fs;
+use std::path;
+use std::sync::Arc;
+
+use quick_xml::events::Event;
+
+use super::{element, read_advance, read_root, read_unicode, ReadContext, XmlReader};
+use crate::error::GlifParserError::{self, GlifInputError};
+use crate::pedantry::Pedantry;
+
+/// What [`scan_ufo_glif`] finds in a .glif.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct GlifHeader {
+    pub name: String,
+    pub unicode: Vec<char>,
+    pub width: Option<u64>,
+    pub height: Option<u64>,
+    /// `<component base>`s, in the order they appear.
+    pub component_bases: Vec<String>,
+}
+
+/// Read just enough of UFO .glif XML to build a cmap or glyph list. Contours and `<lib>` are skipped
+/// over without being parsed, making this much faster than [`read_ufo_glif`](super::read_ufo_glif).
+pub fn scan_ufo_glif(glif: &str) -> Result<GlifHeader, GlifParserError> {
+    let mut ctx = ReadContext::new(glif, Pedantry::default(), false);
+    let mut reader = XmlReader::from_str(glif);
+    scan_glyph(&mut reader, &mut ctx).map_err(|e| e.located(ctx.location()))
+}
+
+pub fn scan_ufo_glif_from_filename<F: AsRef<path::Path>>(filename: F) -> Result<GlifHeader, GlifParserError> {
+    fs::read_to_string(&filename)
+        .map_err(|ioe| GlifParserError::GlifFileIoError(Some(Arc::new(ioe))))
+        .and_then(|glifxml| scan_ufo_glif(&glifxml))
+        .map_err(|e| e.with_filename(filename.as_ref()))
+}
+
+fn scan_glyph<'a>(reader: &mut XmlReader<'a>, ctx: &mut ReadContext<'a>) -> Result<GlifHeader, GlifParserError> {
+    let mut ret = GlifHeader::default();
+
+    let (glif, empty) = read_root(reader, ctx)?;
+    ret.name = ctx.attributes(&glif)?.get("name").ok_or(GlifInputError(String::from("<glyph> has no name")))?.to_string();
+    if empty {
+        return Ok(ret)
+    }
+
+    let (mut advances, mut unicodes, mut outlines) = (0, 0, 0);
+    loop {
+        let (offset, event) = ctx.next(reader)?;
+        if let Event::End(_) = event {
+            return Ok(ret)
+        }
+        let Some((el, el_empty)) = element(event) else { continue };
+        let mut content_read = false;
+        match el.local_name().as_ref() {
+            b"advance" => {
+                ctx.enter("advance", advances, offset);
+                if advances == 0 {
+                    let attrs = ctx.attributes(&el)?;
+                    (ret.width, ret.height) = read_advance(&attrs, ctx)?;
+                }
+                advances += 1;
+            },
+            b"unicode" => {
+                ctx.enter("unicode", unicodes, offset);
+                unicodes += 1;
+                ret.unicode.push(read_unicode(&ctx.attributes(&el)?)?);
+            },
+            b"outline" => {
+                ctx.enter("outline", outlines, offset);
+                if outlines == 0 && !el_empty {
+                    scan_outline(reader, ctx, &mut ret.component_bases)?;
+                    content_read = true;
+                }
+                outlines += 1;
+            },
+            _ => {
+                if !el_empty {
+                    ctx.skip(reader, &el)?;
+                }
+                continue;
+            },
+        }
+        if !el_empty && !content_read {
+            ctx.skip(reader, &el)?;
+        }
+        ctx.leave();
+    }
+}
+
+/// Collect the bases of `<outline>`'s components, skipping its contours unread.
+fn scan_outline<'a>(reader: &mut XmlReader<'a>, ctx: &mut ReadContext<'a>, bases: &mut Vec<String>) -> Result<(), GlifParserError> {
+    let mut components = 0;
+    loop {
+        let (offset, event) = ctx.next(reader)?;
+        if let Event::End(_) = event {
+            return Ok(())
+        }
+        let Some((el, el_empty)) = element(event) else { continue };
+        if el.local_name().as_ref() == b"component" {
+            ctx.enter("component", components, offset);
+            components += 1;
+            let attrs = ctx.attributes(&el)?;
+            bases.push(attrs.get("base").ok_or(GlifInputError(String::from("<component> missing base")))?.to_string());
+            ctx.leave();
+        }
+        if !el_empty {
+            ctx.skip(reader, &el)?;
+        }
+    }
+}
@@ -1,3 +1,4 @@
+mod normalize;
 mod xml;
 pub use xml::{IntoXML, TryIntoXML};
 use xml::*;
@@ -5,9 +6,10 @@ use xml::*;
 use super::{Glif, Lib};
 use crate::codepoint::Codepoint;
 use crate::error::GlifParserError;
-use crate::point::PointData;
+use crate::point::{GlifPoint, PointData, PointType};
 
 use std::fs;
+use std::io;
 use std::mem;
 use std::path::Path;
 
@@ -22,6 +24,63 @@ where
     )))
 }
 
+/// How [`write_ufo_glif_with_options`] writes a .glif. The default is the same output as
+/// [`write_ufo_glif`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WriteOptions {
+    /// Write canonical output, the same as ufonormalizer's, so that running it over our files
+    /// changes nothing.
+    pub normalize: bool,
+    /// When normalizing, how many decimal places to round floats to, as ufonormalizer's
+    /// `--float-precision` (default 10). `None` writes them at full precision.
+    pub float_precision: Option<u8>,
+    /// When normalizing, sort `<lib>` dictionaries by key, as ufonormalizer does, rather than
+    /// keeping the order they were read in.
+    pub sort_lib_keys: bool,
+}
+
+impl Default for WriteOptions {
+    fn default() -> Self {
+        Self { normalize: false, float_precision: Some(10), sort_lib_keys: true }
+    }
+}
+
+impl WriteOptions {
+    /// Options for output matching ufonormalizer's defaults.
+    pub fn normalized() -> Self {
+        Self { normalize: true, ..Default::default() }
+    }
+
+    pub fn float_precision(mut self, float_precision: Option<u8>) -> Self {
+        self.float_precision = float_precision;
+        self
+    }
+
+    pub fn sort_lib_keys(mut self, sort_lib_keys: bool) -> Self {
+        self.sort_lib_keys = sort_lib_keys;
+        self
+    }
+}
+
+pub fn write_ufo_glif_with_options<PD: PointData>(glif: &Glif<PD>, options: &WriteOptions) -> Result<String, GlifParserError> {
+    if options.normalize {
+        check_outline(glif)?;
+        Ok(normalize::write_normalized(glif, options))
+    } else {
+        write_ufo_glif(glif)
+    }
+}
+
+/// Write Glif struct as UFO .glif XML to `writer`, e.g. a socket. If the glif is destined for a
+/// known filename, pass it to have it set on any error.
+pub fn write_ufo_glif_to_writer<W: io::Write, PD: PointData>(glif: &Glif<PD>, writer: W, filename: Option<&Path>) -> Result<(), GlifParserError> {
+    let ret = check_outline(glif).and_then(|()| write_element_to(&glif.xml(), writer));
+    match filename {
+        Some(filename) => ret.map_err(|e| e.with_filename(filename)),
+        None => ret,
+    }
+}
+
 pub fn write_ufo_glif<PD: PointData>(glif: &Glif<PD>) -> Result<String, GlifParserError> {
     let ret = write_ufo_glif_data(glif)?;
     Ok(String::from_utf8(ret)?)
@@ -29,16 +88,104 @@ pub fn write_ufo_glif<PD: PointData>(glif: &Glif<PD>) -> Result<String, GlifPars
 
 /// Write Glif struct to UFO .glif XML
 pub fn write_ufo_glif_data<PD: PointData>(glif: &Glif<PD>) -> Result<Vec<u8>, GlifParserError> {
+    check_outline(glif)?;
+    write_element(&glif.xml())
+}
+
+/// Write Glif struct to legacy (UFO2) GLIF format 1 XML, for toolchains that can't read format 2.
+/// This is lossy: format 1 has no guidelines, images or identifiers, so these are dropped.
+pub fn write_ufo_glif_format_1<PD: PointData>(glif: &Glif<PD>) -> Result<String, GlifParserError> {
+    let ret = write_ufo_glif_format_1_data(glif)?;
+    Ok(String::from_utf8(ret)?)
+}
+
+pub fn write_ufo_glif_format_1_data<PD: PointData>(glif: &Glif<PD>) -> Result<Vec<u8>, GlifParserError> {
+    check_outline(glif)?;
+    write_element(&glif.xml_format_1())
+}
+
+/// Refuse to write an outline whose points couldn't be read back: only `line`, `curve` and `qcurve`
+/// points, and `move` points beginning open contours, can be written.
+pub(crate) fn check_outline<PD: PointData>(glif: &Glif<PD>) -> Result<(), GlifParserError> {
+    for (ci, contour) in glif.outline.iter().flatten().enumerate() {
+        for (pi, point) in contour.iter().enumerate() {
+            match point.ptype {
+                PointType::Move if pi == 0 => {},
+                PointType::Line | PointType::Curve | PointType::QCurve => {},
+                ptype => return Err(GlifParserError::GlifOutlineHasBadPointType { ci, pi, ptype }),
+            }
+        }
+    }
+    Ok(())
+}
+
+fn write_element(glyph: &Element) -> Result<Vec<u8>, GlifParserError> {
+    let mut ret_string: Vec<u8> = Vec::with_capacity(mem::size_of_val(glyph)); // size_of_val is an estimate!
+    write_element_to(glyph, &mut ret_string)?;
+
+    Ok(ret_string)
+}
+
+fn write_element_to<W: io::Write>(glyph: &Element, writer: W) -> Result<(), GlifParserError> {
     let config = xmltree::EmitterConfig::new()
         .perform_indent(true)
         .pad_self_closing(false)
         .autopad_comments(false);
 
-    let glyph = glif.xml();
-    let mut ret_string: Vec<u8> = Vec::with_capacity(mem::size_of_val(&glyph)); // size_of_val is an estimate!
-    glyph.write_with_config(&mut ret_string, config)?;
+    glyph.write_with_config(writer, config)?;
 
-    Ok(ret_string)
+    Ok(())
+}
+
+fn strip_identifiers(el: &mut Element) {
+    el.attributes.remove("identifier");
+    for child in el.children.iter_mut() {
+        if let XMLNode::Element(child_el) = child {
+            strip_identifiers(child_el);
+        }
+    }
+}
+
+impl<PD: PointData> Glif<PD> {
+    /// Downgrades the format 2 `<glyph>` to format 1. Anchors become contours of a single named
+    /// `move` point, as they were stored in UFO2.
+    pub fn xml_format_1(&self) -> Element {
+        let mut glyph = self.xml();
+        glyph.attributes.insert("format".to_owned(), "1".to_string());
+        glyph.attributes.remove("formatMinor");
+        glyph.children.retain(|c| !matches!(c.as_element().map(|el| el.name.as_str()), Some("anchor" | "guideline" | "image")));
+        strip_identifiers(&mut glyph);
+
+        let anchor_contours: Vec<Element> = self.anchors.iter().filter_map(|anchor| {
+            let Some(class) = &anchor.class else {
+                log::warn!("Dropping anchor at ({}, {}) without a name, as GLIF format 1 can't store it", anchor.x, anchor.y);
+                return None
+            };
+            let gpoint = GlifPoint::from_x_y_type((anchor.x, anchor.y), PointType::Move).name(Some(class.clone()));
+            let mut contour_node = Element::new("contour");
+            contour_node.children.push(XMLNode::Element(gpoint.xml()));
+            Some(contour_node)
+        }).collect();
+
+        if anchor_contours.is_empty() {
+            return glyph
+        }
+
+        let outline_idx = match glyph.children.iter().position(|c| c.as_element().map(|el| el.name == "outline").unwrap_or(false)) {
+            Some(idx) => idx,
+            None => {
+                // <outline> goes before <note> and <lib>
+                let idx = glyph.children.iter().position(|c| matches!(c.as_element().map(|el| el.name.as_str()), Some("note" | "lib"))).unwrap_or(glyph.children.len());
+                glyph.children.insert(idx, XMLNode::Element(Element::new("outline")));
+                idx
+            }
+        };
+        if let XMLNode::Element(outline_node) = &mut glyph.children[outline_idx] {
+            outline_node.children.extend(anchor_contours.into_iter().map(XMLNode::Element));
+        }
+
+        glyph
+    }
 }
 
 impl<PD: PointData> IntoXML for Glif<PD> {
@@ -46,8 +193,12 @@ impl<PD: PointData> IntoXML for Glif<PD> {
         let mut glyph = Element::new("glyph");
         glyph.attributes.insert("name".to_owned(), self.name.to_string());
         glyph.attributes.insert("format".to_owned(), "2".to_string());
+        let format_minor = self.required_format_minor();
+        if format_minor != 0 {
+            glyph.attributes.insert("formatMinor".to_owned(), format_minor.to_string());
+        }
 
-        if self.width.is_some() {
+        if self.width.is_some() || self.height.is_some() {
             glyph.children.push(XMLNode::Element(self.advance_xml()));
         };
 
@@ -55,14 +206,20 @@ impl<PD: PointData> IntoXML for Glif<PD> {
             glyph.children.push(XMLNode::Element(hex.xml()));
         }
 
+        // Objects with a lib or data but no identifier are given one, in the order they're written
+        let mut identifiers = self.generated_identifiers();
+
         for anchor in self.anchors.iter() {
-            glyph.children.push(XMLNode::Element(anchor.xml()));
+            glyph.children.push(XMLNode::Element(identifiers.element(anchor.xml())));
         }
 
-        let mut outline_node = self.outline.as_ref().map(|o|o.xml()).unwrap_or(Element::new("outline"));
+        let mut outline_node = Element::new("outline");
+        for contour in self.outline.iter().flatten() {
+            outline_node.children.push(XMLNode::Element(identifiers.contour(contour)));
+        }
 
         for component in &self.components.vec {
-            outline_node.children.push(XMLNode::Element(component.xml()));
+            outline_node.children.push(XMLNode::Element(identifiers.element(component.xml())));
         }
 
         if outline_node.children.len() >= 1 {
@@ -77,7 +234,7 @@ impl<PD: PointData> IntoXML for Glif<PD> {
         }
 
         for guideline in &self.guidelines {
-            glyph.children.push(XMLNode::Element(guideline.xml()));
+            glyph.children.push(XMLNode::Element(identifiers.element(guideline.xml())));
         }
 
         if let Some(note) = self.note.as_ref().map(|n| n.clone()) {
@@ -88,7 +245,7 @@ impl<PD: PointData> IntoXML for Glif<PD> {
         }
 
         #[rustfmt::skip]
-        let mut lib = match &self.lib {
+        let mut lib = match self.lib_with_object_data().as_ref() {
             Lib::Plist(lib_node) => {
                 let mut plist_buf: Vec<u8> = vec![];
                 match plist::to_writer_xml(&mut plist_buf, &lib_node).map(|()|Element::parse(plist_buf.as_slice())) {
@@ -0,0 +1,254 @@
+//! Canonical .glif output, byte for byte as ufonormalizer writes it: tab indentation, a fixed
+//! element and attribute order, default values omitted, and floats rounded to a set precision.
+
+use super::xml::Element;
+use super::{IntoXML, WriteOptions};
+use crate::glif::{Glif, Lib};
+use crate::point::PointData;
+
+const XML_DECLARATION: &str = r#"<?xml version="1.0" encoding="UTF-8"?>"#;
+
+/// Children of `<glyph>` other than `<lib>`, in the order ufonormalizer writes them.
+const ELEMENT_ORDER: [&str; 7] = ["advance", "unicode", "note", "image", "guideline", "anchor", "outline"];
+
+/// Attributes are written in this order, followed by any others in alphabetical order.
+const ATTRIBUTE_ORDER: [&str; 17] = [
+    "name", "base", "format", "fileName", "x", "y", "angle", "xScale", "xyScale", "yxScale", "yScale",
+    "xOffset", "yOffset", "type", "smooth", "color", "identifier",
+];
+
+pub(super) fn write_normalized<PD: PointData>(glif: &Glif<PD>, options: &WriteOptions) -> String {
+    let glyph = glif.xml();
+    let mut writer = Writer { out: format!("{}\n", XML_DECLARATION), level: 0, options };
+
+    writer.begin("glyph", &writer.attributes(&glyph));
+    let mut unicodes = vec![];
+    for tag in ELEMENT_ORDER {
+        for el in glyph.children.iter().filter_map(|c| c.as_element()).filter(|el| el.name == tag) {
+            let attributes = writer.attributes(el);
+            match tag {
+                "advance" if attributes.is_empty() => {},
+                "unicode" => if !unicodes.contains(&attributes) {
+                    writer.simple(tag, &attributes, None);
+                    unicodes.push(attributes);
+                },
+                "note" => writer.note(el.get_text().unwrap_or_default().as_ref()),
+                "outline" => writer.outline(el),
+                _ => writer.simple(tag, &attributes, None),
+            }
+        }
+    }
+    writer.lib(&glif.lib_with_object_data());
+    writer.end("glyph");
+
+    writer.out
+}
+
+struct Writer<'a> {
+    out: String,
+    level: usize,
+    options: &'a WriteOptions,
+}
+
+impl<'a> Writer<'a> {
+    fn line(&mut self, text: &str) {
+        if !text.is_empty() {
+            self.out.extend(std::iter::repeat_n('\t', self.level));
+        }
+        self.out.push_str(text);
+        self.out.push('\n');
+    }
+
+    fn begin(&mut self, tag: &str, attributes: &[(String, String)]) {
+        self.line(&format!("<{}{}>", tag, attributes_string(attributes)));
+        self.level += 1;
+    }
+
+    fn end(&mut self, tag: &str) {
+        self.level -= 1;
+        self.line(&format!("</{}>", tag));
+    }
+
+    fn simple(&mut self, tag: &str, attributes: &[(String, String)], value: Option<&str>) {
+        match value {
+            Some(value) => self.line(&format!("<{0}{1}>{2}</{0}>", tag, attributes_string(attributes), escape_text(value))),
+            None => self.line(&format!("<{}{}/>", tag, attributes_string(attributes))),
+        }
+    }
+
+    fn float(&self, value: f64) -> String {
+        let formatted = match self.options.float_precision {
+            Some(precision) => format!("{:.*}", precision as usize, value),
+            None => value.to_string(),
+        };
+        let formatted = match formatted.contains('.') {
+            true => formatted.trim_end_matches('0').trim_end_matches('.'),
+            false => formatted.as_str(),
+        };
+        match formatted {
+            "-0" => String::from("0"),
+            f => f.to_string(),
+        }
+    }
+
+    /// `el`'s attributes, with numbers reformatted, default values dropped, and sorted.
+    fn attributes(&self, el: &Element) -> Vec<(String, String)> {
+        let mut attributes = vec![];
+        for (key, value) in el.attributes.iter() {
+            let value = match key.as_str() {
+                "x" | "y" | "angle" | "width" | "height" | "xScale" | "xyScale" | "yxScale" | "yScale" | "xOffset" | "yOffset" => {
+                    let Ok(f) = value.parse::<f64>() else { continue };
+                    let default = match key.as_str() {
+                        "xScale" | "yScale" => Some(1.),
+                        "xyScale" | "yxScale" | "xOffset" | "yOffset" | "width" | "height" => Some(0.),
+                        _ => None,
+                    };
+                    if default == Some(f) {
+                        continue;
+                    }
+                    self.float(f)
+                },
+                "color" => value.split(',').map(|c| c.trim().parse().map(|c| self.float(c)).unwrap_or_else(|_| c.to_string())).collect::<Vec<_>>().join(","),
+                "hex" => u32::from_str_radix(value, 16).map(|u| format!("{:04X}", u)).unwrap_or_else(|_| value.clone()),
+                "smooth" if value != "yes" => continue,
+                _ => value.clone(),
+            };
+            attributes.push((key.clone(), value));
+        }
+        attributes.sort_by_key(|(key, _)| (ATTRIBUTE_ORDER.iter().position(|a| a == key).unwrap_or(ATTRIBUTE_ORDER.len()), key.clone()));
+        attributes
+    }
+
+    /// The note's text, dedented, on lines of its own.
+    fn note(&mut self, text: &str) {
+        if text.trim().is_empty() {
+            return
+        }
+        let mut lines: Vec<&str> = text.trim_matches('\n').lines().map(|l| l.trim_end()).collect();
+        while lines.last() == Some(&"") {
+            lines.pop();
+        }
+        let dedent = lines.iter()
+            .filter(|l| !l.is_empty())
+            .map(|l| l.len() - l.trim_start().len())
+            .min()
+            .unwrap_or(0);
+        self.begin("note", &[]);
+        for line in lines {
+            self.line(&escape_text(line.get(dedent..).unwrap_or_default()));
+        }
+        self.end("note");
+    }
+
+    fn outline(&mut self, outline: &Element) {
+        self.begin("outline", &[]);
+        for el in outline.children.iter().filter_map(|c| c.as_element()) {
+            let attributes = self.attributes(el);
+            if el.name != "contour" {
+                self.simple(&el.name, &attributes, None);
+                continue;
+            }
+            let points: Vec<_> = el.children.iter().filter_map(|c| c.as_element()).collect();
+            if points.is_empty() {
+                continue;
+            }
+            self.begin("contour", &attributes);
+            for point in points {
+                let attributes = self.attributes(point);
+                self.simple("point", &attributes, None);
+            }
+            self.end("contour");
+        }
+        self.end("outline");
+    }
+
+    fn lib(&mut self, lib: &Lib) {
+        match lib {
+            Lib::Plist(dict) if !dict.is_empty() => {
+                self.begin("lib", &[]);
+                self.plist_dict(dict);
+                self.end("lib");
+            },
+            Lib::Xml(el) => self.element(el),
+            _ => {},
+        }
+    }
+
+    fn plist_dict(&mut self, dict: &plist::Dictionary) {
+        if dict.is_empty() {
+            return self.simple("dict", &[], None)
+        }
+        let mut entries: Vec<_> = dict.iter().collect();
+        if self.options.sort_lib_keys {
+            entries.sort_by_key(|(key, _)| *key);
+        }
+        self.begin("dict", &[]);
+        for (key, value) in entries {
+            self.simple("key", &[], Some(key));
+            self.plist_value(value);
+        }
+        self.end("dict");
+    }
+
+    fn plist_value(&mut self, value: &plist::Value) {
+        use plist::Value;
+        match value {
+            Value::Dictionary(dict) => self.plist_dict(dict),
+            Value::Array(array) if array.is_empty() => self.simple("array", &[], None),
+            Value::Array(array) => {
+                self.begin("array", &[]);
+                for value in array {
+                    self.plist_value(value);
+                }
+                self.end("array");
+            },
+            Value::String(s) => self.simple("string", &[], Some(s)),
+            Value::Integer(i) => self.simple("integer", &[], Some(&i.to_string())),
+            Value::Real(f) => self.simple("real", &[], Some(&self.float(*f))),
+            Value::Boolean(true) => self.simple("true", &[], None),
+            Value::Boolean(false) => self.simple("false", &[], None),
+            Value::Data(data) => self.simple("data", &[], Some(&base64(data))),
+            Value::Date(date) => self.simple("date", &[], Some(&date.to_xml_format())),
+            _ => log::warn!("Can't write {:?} to a .glif <lib>, skipping", value),
+        }
+    }
+
+    /// An arbitrary element, as when `<lib>` couldn't be read as a plist.
+    fn element(&mut self, el: &Element) {
+        let attributes: Vec<(String, String)> = el.attributes.iter().map(|(k, v)| (k.clone(), v.clone())).collect();
+        let children: Vec<_> = el.children.iter().filter_map(|c| c.as_element()).collect();
+        match (children.is_empty(), el.get_text()) {
+            (true, text) => self.simple(&el.name, &attributes, text.as_deref()),
+            (false, _) => {
+                self.begin(&el.name, &attributes);
+                for child in children {
+                    self.element(child);
+                }
+                self.end(&el.name);
+            },
+        }
+    }
+}
+
+fn attributes_string(attributes: &[(String, String)]) -> String {
+    attributes.iter().map(|(k, v)| format!(" {}=\"{}\"", k, escape_text(v).replace('"', "&quot;"))).collect()
+}
+
+fn escape_text(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
+
+fn base64(data: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut ret = String::with_capacity(data.len().div_ceil(3) * 4);
+    for chunk in data.chunks(3) {
+        let n = chunk.iter().enumerate().fold(0u32, |n, (i, b)| n | (*b as u32) << (16 - 8 * i));
+        for i in 0..4 {
+            match i <= chunk.len() {
+                true => ret.push(ALPHABET[(n >> (18 - 6 * i) & 63) as usize] as char),
+                false => ret.push('='),
+            }
+        }
+    }
+    ret
+}
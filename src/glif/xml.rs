@@ -14,7 +14,12 @@ pub trait XMLConversion: IntoXML + FromXML {}
 impl<PD: PointData> Glif<PD> {
     pub fn advance_xml(&self) -> Element {
         let mut advanceel = Element::new("advance");
-        advanceel.attributes.insert("width".to_owned(), self.width.unwrap().to_string());
+        if let Some(width) = self.width {
+            advanceel.attributes.insert("width".to_owned(), width.to_string());
+        }
+        if let Some(height) = self.height {
+            advanceel.attributes.insert("height".to_owned(), height.to_string());
+        }
         advanceel
     }
 }
@@ -0,0 +1,284 @@
+//! [`GlyphSet`], a UFO glyphs directory: its `contents.plist`, `layerinfo.plist` and .glif files.
+//!
+//! This is only the glyph-directory level of the UFO spec. Reading the rest of a UFO, such as
+//! `layercontents.plist` or `fontinfo.plist`, is left to e.g. norad.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::str::FromStr as _;
+
+use crate::color::Color;
+use crate::component::{ComponentProvider, GlifComponent};
+use crate::error::GlifParserError::{self, GlifInputError};
+use crate::glif::{self, user_name_to_file_name, Glif};
+use crate::pedantry::Pedantry;
+use crate::point::PointData;
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+mod write;
+
+pub const CONTENTS_PLIST: &str = "contents.plist";
+pub const LAYERINFO_PLIST: &str = "layerinfo.plist";
+
+/// A glyphs directory's `layerinfo.plist`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct LayerInfo {
+    pub color: Option<Color>,
+    pub lib: plist::Dictionary,
+}
+
+/// A UFO glyphs directory, mapping glyph names to [`Glif`]s. Glyphs are read when first asked for,
+/// or all at once by [`GlyphSet::load_all`].
+#[derive(Clone, Debug)]
+pub struct GlyphSet<PD: PointData> {
+    path: PathBuf,
+    /// Glyph names to file names, as in `contents.plist`.
+    contents: BTreeMap<String, String>,
+    pub layer_info: LayerInfo,
+    pub pedantry: Pedantry,
+    glyphs: HashMap<String, Glif<PD>>,
+    /// Loaded glyphs which may have changed since they were read, and so are written on save.
+    modified: HashSet<String>,
+    /// The files in `contents.plist` as it was opened or last saved, lowercased, which saving
+    /// deletes if they're no longer in it; `None` if it's been neither.
+    files: Option<HashSet<String>>,
+}
+
+impl<PD: PointData> GlyphSet<PD> {
+    /// A glyph set with no glyphs, to be saved to `path`, which needn't exist yet but must be empty
+    /// if it does.
+    pub fn new<P: AsRef<Path>>(path: P) -> Self {
+        Self {
+            path: path.as_ref().to_path_buf(),
+            contents: BTreeMap::new(),
+            layer_info: LayerInfo::default(),
+            pedantry: Pedantry::default(),
+            glyphs: HashMap::new(),
+            modified: HashSet::new(),
+            files: None,
+        }
+    }
+
+    /// Open the glyphs directory at `path`, reading its `contents.plist` and `layerinfo.plist` (if
+    /// any) but no glyphs.
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self, GlifParserError> {
+        Self::open_pedantic(path, Pedantry::default())
+    }
+
+    pub fn open_pedantic<P: AsRef<Path>>(path: P, pedantry: Pedantry) -> Result<Self, GlifParserError> {
+        let path = path.as_ref().to_path_buf();
+        let contents_path = path.join(CONTENTS_PLIST);
+        let contents = read_contents(&contents_path).map_err(|e| e.with_filename(&contents_path))?;
+        let layerinfo_path = path.join(LAYERINFO_PLIST);
+        let layer_info = match layerinfo_path.exists() {
+            true => read_layer_info(&layerinfo_path).map_err(|e| e.with_filename(&layerinfo_path))?,
+            false => LayerInfo::default(),
+        };
+
+        let files = Some(contents.values().map(|f| f.to_lowercase()).collect());
+        Ok(Self { path, contents, layer_info, pedantry, glyphs: HashMap::new(), modified: HashSet::new(), files })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Glyph names to file names, as in `contents.plist`.
+    pub fn contents(&self) -> &BTreeMap<String, String> {
+        &self.contents
+    }
+
+    /// Glyph names, in code point order.
+    pub fn names(&self) -> impl Iterator<Item = &String> {
+        self.contents.keys()
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.contents.contains_key(name)
+    }
+
+    pub fn len(&self) -> usize {
+        self.contents.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.contents.is_empty()
+    }
+
+    /// The path of the .glif file of the glyph `name`.
+    pub fn filename(&self, name: &str) -> Option<PathBuf> {
+        self.contents.get(name).map(|f| self.path.join(f))
+    }
+
+    /// Read the glyph `name` from disk, whether or not it's been loaded. Its filename and its
+    /// components' are set from `contents.plist`.
+    pub fn read(&self, name: &str) -> Result<Glif<PD>, GlifParserError> {
+        read_glyph(&self.path, &self.contents, self.pedantry, name)
+    }
+
+    /// The glyph `name`, read from disk if it's not yet loaded.
+    pub fn get(&mut self, name: &str) -> Result<&Glif<PD>, GlifParserError> {
+        self.load(name)?;
+        self.glyphs.get(name).ok_or_else(|| GlifParserError::GlyphNotInGlyphSet(name.to_string()))
+    }
+
+    /// As [`GlyphSet::get`], to edit the glyph. It's then counted as modified, and so written on
+    /// the next save.
+    pub fn get_mut(&mut self, name: &str) -> Result<&mut Glif<PD>, GlifParserError> {
+        self.load(name)?;
+        self.modified.insert(name.to_string());
+        self.glyphs.get_mut(name).ok_or_else(|| GlifParserError::GlyphNotInGlyphSet(name.to_string()))
+    }
+
+    fn load(&mut self, name: &str) -> Result<(), GlifParserError> {
+        if !self.glyphs.contains_key(name) {
+            let glif = self.read(name)?;
+            self.glyphs.insert(name.to_string(), glif);
+        }
+        Ok(())
+    }
+
+    pub fn is_loaded(&self, name: &str) -> bool {
+        self.glyphs.contains_key(name)
+    }
+
+    /// Whether the glyph `name` has been inserted, renamed or borrowed by [`GlyphSet::get_mut`]
+    /// since it was read, and so is written on save.
+    pub fn is_modified(&self, name: &str) -> bool {
+        self.modified.contains(name)
+    }
+
+    /// The glyphs read so far, in no particular order.
+    pub fn loaded(&self) -> impl Iterator<Item = (&String, &Glif<PD>)> {
+        self.glyphs.iter()
+    }
+
+    /// Read every glyph not yet loaded.
+    pub fn load_all(&mut self) -> Result<(), GlifParserError> {
+        for name in self.contents.keys() {
+            if !self.glyphs.contains_key(name) {
+                let glif = read_glyph(&self.path, &self.contents, self.pedantry, name)?;
+                self.glyphs.insert(name.clone(), glif);
+            }
+        }
+        Ok(())
+    }
+
+    /// Add `glif` under its name, replacing any glyph of that name. A new glyph is given a file name
+    /// by the UFO 3 algorithm, unique in the directory; its filename and its components' are set.
+    /// It's written on the next save.
+    pub fn insert(&mut self, mut glif: Glif<PD>) {
+        let file = match self.contents.get(&glif.name) {
+            Some(file) => file.clone(),
+            None => {
+                let in_use: HashSet<String> = self.contents.values().map(|f| f.to_lowercase()).collect();
+                user_name_to_file_name(&glif.name, "", ".glif", &in_use)
+            },
+        };
+        glif.filename = Some(self.path.join(&file));
+        set_component_filenames(&self.path, &self.contents, &mut glif);
+        self.contents.insert(glif.name.clone(), file);
+        self.modified.insert(glif.name.clone());
+        self.glyphs.insert(glif.name.clone(), glif);
+    }
+
+    /// Remove the glyph `name`, returning it if it was loaded. Its file is deleted on the next
+    /// save.
+    pub fn remove(&mut self, name: &str) -> Option<Glif<PD>> {
+        self.contents.remove(name);
+        self.modified.remove(name);
+        self.glyphs.remove(name)
+    }
+
+    /// Rename the glyph `old` to `new`, replacing any glyph named `new`. It's given a new file name,
+    /// and its old file is deleted on the next save. Components referring to it are not changed.
+    pub fn rename(&mut self, old: &str, new: &str) -> Result<(), GlifParserError> {
+        let mut glif = match self.glyphs.remove(old) {
+            Some(glif) => glif,
+            None => self.read(old)?,
+        };
+        self.contents.remove(old);
+        self.modified.remove(old);
+        self.remove(new);
+        glif.name = new.to_string();
+        self.insert(glif);
+        Ok(())
+    }
+
+    /// As [`GlyphSet::load_all`], reading glyphs on all cores.
+    #[cfg(feature = "parallel")]
+    pub fn load_all_parallel(&mut self) -> Result<(), GlifParserError> where PD: Send {
+        let (path, contents, pedantry) = (&self.path, &self.contents, self.pedantry);
+        let unloaded: Vec<&String> = contents.keys().filter(|name| !self.glyphs.contains_key(*name)).collect();
+        let read = unloaded
+            .into_par_iter()
+            .map(|name| read_glyph(path, contents, pedantry, name).map(|glif| (name.clone(), glif)))
+            .collect::<Result<Vec<_>, _>>()?;
+        self.glyphs.extend(read);
+        Ok(())
+    }
+}
+
+/// Components resolve to the set's glyphs, as edited if loaded, else as on disk.
+impl<PD: PointData> ComponentProvider<PD> for GlyphSet<PD> {
+    fn base_glif(&self, component: &GlifComponent) -> Result<Glif<PD>, GlifParserError> {
+        match self.glyphs.get(&component.base) {
+            Some(glif) => Ok(glif.clone()),
+            None => self.read(&component.base),
+        }
+    }
+}
+
+fn read_glyph<PD: PointData>(path: &Path, contents: &BTreeMap<String, String>, pedantry: Pedantry, name: &str) -> Result<Glif<PD>, GlifParserError> {
+    let file = contents.get(name).ok_or_else(|| GlifParserError::GlyphNotInGlyphSet(name.to_string()))?;
+    let mut glif: Glif<PD> = glif::read_from_filename_pedantic(path.join(file), pedantry)?;
+    if glif.name != name {
+        log::warn!("Glyph {:?} in {} is named {:?} in contents.plist", glif.name, file, name);
+    }
+    set_component_filenames(path, contents, &mut glif);
+    Ok(glif)
+}
+
+fn set_component_filenames<PD: PointData>(path: &Path, contents: &BTreeMap<String, String>, glif: &mut Glif<PD>) {
+    for component in glif.components.vec.iter_mut() {
+        match contents.get(&component.base) {
+            Some(file) => component.filename = Some(path.join(file)),
+            None => component.set_file_name(path.join(glif::name_to_filename(&component.base, true))),
+        }
+    }
+}
+
+fn read_plist_dictionary(path: &Path) -> Result<plist::Dictionary, GlifParserError> {
+    plist::Value::from_file(path)
+        .map_err(|e| GlifInputError(e.to_string()))?
+        .into_dictionary()
+        .ok_or_else(|| GlifInputError(String::from("plist is not a dictionary")))
+}
+
+fn read_contents(path: &Path) -> Result<BTreeMap<String, String>, GlifParserError> {
+    let mut contents = BTreeMap::new();
+    for (name, file) in read_plist_dictionary(path)? {
+        let file = file.into_string().ok_or_else(|| GlifInputError(format!("file name of {:?} is not a string", name)))?;
+        // Don't let contents.plist point outside the glyphs directory
+        if file.is_empty() || file.contains(['/', '\\']) || file == "." || file == ".." {
+            return Err(GlifInputError(format!("file name {:?} of {:?} is not a file in the glyphs directory", file, name)))
+        }
+        contents.insert(name, file);
+    }
+    Ok(contents)
+}
+
+fn read_layer_info(path: &Path) -> Result<LayerInfo, GlifParserError> {
+    let mut layer_info = LayerInfo::default();
+    for (key, value) in read_plist_dictionary(path)? {
+        match (key.as_str(), value) {
+            ("color", plist::Value::String(color)) => layer_info.color = Some(Color::from_str(&color)?),
+            ("lib", plist::Value::Dictionary(lib)) => layer_info.lib = lib,
+            ("color" | "lib", _) => return Err(GlifInputError(format!("{} has the wrong type", key))),
+            _ => log::warn!("Ignored unknown key {:?} in layerinfo.plist", key),
+        }
+    }
+    Ok(layer_info)
+}
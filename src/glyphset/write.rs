@@ -0,0 +1,117 @@
+//! Saving a [`GlyphSet`] back to its glyphs directory.
+
+use std::collections::HashSet;
+use std::fs;
+use std::io::{self, Write as _};
+use std::path::Path;
+use std::sync::Arc;
+
+use super::{GlyphSet, LayerInfo, CONTENTS_PLIST, LAYERINFO_PLIST};
+use crate::error::GlifParserError;
+use crate::glif::{write_with_options, WriteOptions};
+use crate::point::PointData;
+
+impl<PD: PointData> GlyphSet<PD> {
+    /// Write the glyph set to its directory, creating it if need be. See
+    /// [`GlyphSet::save_with_options`].
+    pub fn save(&mut self) -> Result<(), GlifParserError> {
+        self.save_with_options(&WriteOptions::default())
+    }
+
+    /// Write the glyph set to its directory, creating it if need be:
+    ///
+    /// * modified glyphs (see [`GlyphSet::is_modified`]) are written with `options`, unless their
+    ///   file already holds exactly that. Others are left as they are on disk, even if loaded, so
+    ///   as not to rewrite files other tools wrote;
+    /// * `contents.plist` and `layerinfo.plist` are regenerated, the latter removed if empty;
+    /// * .glif files which were in `contents.plist` when the glyph set was opened or last saved,
+    ///   but no longer are, e.g. of removed or renamed glyphs, are deleted. Others are left be.
+    ///
+    /// A glyph set made with [`GlyphSet::new`] refuses to be saved to a directory which isn't
+    /// empty, so as not to clobber another glyph set there; open that instead.
+    ///
+    /// Each file is written to a temporary file which is then renamed over it, so that an
+    /// interrupted save leaves every file either as it was or as it should be.
+    pub fn save_with_options(&mut self, options: &WriteOptions) -> Result<(), GlifParserError> {
+        if self.files.is_none() && fs::read_dir(&self.path).map(|mut entries| entries.next().is_some()).unwrap_or(false) {
+            let e = io::Error::new(io::ErrorKind::DirectoryNotEmpty, "Won't save a new glyph set over a directory which isn't empty");
+            return Err(io_error(e, &self.path))
+        }
+        fs::create_dir_all(&self.path).map_err(|e| io_error(e, &self.path))?;
+
+        for (name, glif) in self.glyphs.iter().filter(|(name, _)| self.modified.contains(*name)) {
+            let Some(file) = self.contents.get(name) else { continue };
+            let path = self.path.join(file);
+            let xml = write_with_options(glif, options).map_err(|e| e.with_filename(&path))?;
+            write_if_changed(&path, xml.as_bytes())?;
+        }
+
+        let contents = plist::Value::Dictionary(self.contents.iter().map(|(name, file)| (name.clone(), plist::Value::String(file.clone()))).collect());
+        write_if_changed(&self.path.join(CONTENTS_PLIST), &plist_xml(&contents)?)?;
+
+        let layerinfo_path = self.path.join(LAYERINFO_PLIST);
+        match layer_info_plist(&self.layer_info) {
+            Some(layer_info) => write_if_changed(&layerinfo_path, &plist_xml(&layer_info)?)?,
+            None if layerinfo_path.exists() => fs::remove_file(&layerinfo_path).map_err(|e| io_error(e, &layerinfo_path))?,
+            None => {},
+        }
+
+        let in_use: HashSet<String> = self.contents.values().map(|f| f.to_lowercase()).collect();
+        self.remove_orphans(&in_use)?;
+        self.files = Some(in_use);
+        Ok(())
+    }
+
+    /// Delete the .glif files which were in `contents.plist` when last opened or saved, but aren't
+    /// in `in_use`.
+    fn remove_orphans(&self, in_use: &HashSet<String>) -> Result<(), GlifParserError> {
+        let Some(old) = &self.files else { return Ok(()) };
+        for entry in fs::read_dir(&self.path).map_err(|e| io_error(e, &self.path))? {
+            let path = entry.map_err(|e| io_error(e, &self.path))?.path();
+            let Some(file) = path.file_name().and_then(|f| f.to_str()).map(str::to_lowercase) else { continue };
+            if file.ends_with(".glif") && old.contains(&file) && !in_use.contains(&file) && path.is_file() {
+                fs::remove_file(&path).map_err(|e| io_error(e, &path))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+fn io_error(e: io::Error, path: &Path) -> GlifParserError {
+    GlifParserError::GlifFileIoError(Some(Arc::new(e))).with_filename(path)
+}
+
+fn layer_info_plist(layer_info: &LayerInfo) -> Option<plist::Value> {
+    let mut dict = plist::Dictionary::new();
+    if let Some(color) = layer_info.color {
+        dict.insert(String::from("color"), plist::Value::String(color.to_string()));
+    }
+    if !layer_info.lib.is_empty() {
+        dict.insert(String::from("lib"), plist::Value::Dictionary(layer_info.lib.clone()));
+    }
+    (!dict.is_empty()).then_some(plist::Value::Dictionary(dict))
+}
+
+fn plist_xml(value: &plist::Value) -> Result<Vec<u8>, GlifParserError> {
+    let mut ret = vec![];
+    value.to_writer_xml(&mut ret).map_err(|e| GlifParserError::XmlWriteError(e.to_string()))?;
+    ret.push(b'\n');
+    Ok(ret)
+}
+
+/// Atomically replace `path` with `data`, unless it already holds it.
+fn write_if_changed(path: &Path, data: &[u8]) -> Result<(), GlifParserError> {
+    if fs::read(path).map(|old| old == data).unwrap_or(false) {
+        return Ok(())
+    }
+    let file_name = path.file_name().and_then(|f| f.to_str()).unwrap_or_default();
+    let temp = path.with_file_name(format!(".{}.{}.tmp", file_name, std::process::id()));
+    let written = fs::File::create(&temp)
+        .and_then(|mut f| f.write_all(data).and_then(|()| f.sync_all()))
+        .and_then(|()| fs::rename(&temp, path));
+    if let Err(e) = written {
+        let _ = fs::remove_file(&temp);
+        return Err(io_error(e, path))
+    }
+    Ok(())
+}
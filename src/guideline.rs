@@ -34,6 +34,9 @@ pub struct Guideline<GD: PointData> {
     pub color: Option<Color>,
     pub identifier: Option<String>,
     pub data: GD,
+    /// The guideline's lib in `public.objectLibs`
+    #[cfg_attr(feature = "glifserde", serde(default))]
+    pub lib: plist::Dictionary,
 }
 
 impl<GD: PointData> Guideline<GD> {
@@ -5,7 +5,7 @@ mod xml;
 use std::io;
 use std::fs;
 use std::path;
-use std::rc::Rc;
+use std::sync::Arc;
 
 use crate::color::Color;
 use crate::error::GlifParserError;
@@ -40,10 +40,11 @@ pub enum DataOrBitmap {
 }
 
 impl DataOrBitmap {
-    fn unwrap_data(&self) -> &Vec<u8> {
+    /// The undecoded file data, or an error if it has already been decoded to a bitmap.
+    pub fn data(&self) -> Result<&Vec<u8>, GlifParserError> {
         match self {
-            DataOrBitmap::Data(v) => &v,
-            DataOrBitmap::Bitmap{..} => panic!("Unwrapped data of bitmap variant")
+            DataOrBitmap::Data(v) => Ok(v),
+            DataOrBitmap::Bitmap{..} => Err(GlifParserError::TypeConversionError{req_type: "DataOrBitmap", req_variant: String::from("Data")}),
         }
     }
 }
@@ -63,7 +64,7 @@ impl ImageData {
     }
 
     pub fn guess_codec(&self) -> ImageCodec {
-        let codec = match self.data.unwrap_data().chunks(12).next() {
+        let codec = match self.data.data().ok().and_then(|d| d.chunks(12).next()) {
             Some(&[0x42, 0x4D, _, _, _, _, _, _, _, _, _, _]) => ImageCodec::BMP,
             Some(&[0xFF, 0xD8, _, _, _, _, _, _, _, _, _, _]) => ImageCodec::JPEG,
             Some(&[0x89, 0x50, 0x4E, 0x47, _, _, _, _, _, _, _, _]) => ImageCodec::PNG,
@@ -190,7 +191,7 @@ impl Image {
     pub fn load(&mut self) -> Result<(), GlifParserError> {
         self.data.data = DataOrBitmap::Data(fs::read(&self.filename).or_else(|e| {
             self.data.state = DataLoadState::TriedAndFailed;
-            Err(GlifParserError::ImageIoError(Some(Rc::new(e))))
+            Err(GlifParserError::ImageIoError(Some(Arc::new(e))))
         })?);
         self.codec = self.data.guess_codec();
         self.data.state = DataLoadState::Loaded;
@@ -201,8 +202,7 @@ impl Image {
         match self.data.state {
             DataLoadState::NotTried => Err(GlifParserError::ImageNotLoaded),
             DataLoadState::TriedAndFailed => Err(GlifParserError::ImageIoError(None)),
-            DataLoadState::Loaded => Ok(self.data.data.unwrap_data().clone()),
-            _ => unimplemented!()
+            DataLoadState::Loaded | DataLoadState::LoadedDecodeFailed | DataLoadState::Decoded => self.data.data.data().cloned(),
         }
     }
 
@@ -213,7 +213,7 @@ impl Image {
             DataOrBitmap::Data(d) => d,
             DataOrBitmap::Bitmap { .. } => Err(GlifParserError::ImageIoError(None))?
         };
-        let reader = Reader::new(io::Cursor::new(raw_data)).with_guessed_format().or_else(|e|Err(GlifParserError::ImageIoError(Some(Rc::new(e)))))?;
+        let reader = Reader::new(io::Cursor::new(raw_data)).with_guessed_format().or_else(|e|Err(GlifParserError::ImageIoError(Some(Arc::new(e)))))?;
         #[cfg(not(feature = "more-image-formats"))]
         if !(reader.format() == Some(image::ImageFormat::Png)) {
             self.data.state = DataLoadState::LoadedDecodeFailed;
@@ -7,15 +7,14 @@ use super::GlifImage;
 impl TryIntoXML for GlifImage {
     fn try_xml(&self) -> GlifParserResult<Element> {
         let mut image_node = Element::new("image");
-        let image_fn = self.filename.to_string_lossy().into_owned();
-        if self.filename.to_str().map(|s: &str| s.len()).unwrap_or(0) != image_fn.as_str().len() {
-            log::error!("image filename `{:?}` not UTF8, skipping!", self.filename);
-            return Err(GlifParserError::GlifNotUtf8)
-        } else {
-            image_node.attributes.insert(
-                "fileName".to_string(),
-                self.filename.to_str().unwrap().to_string(),
-            );
+        match self.filename.to_str() {
+            Some(image_fn) => {
+                image_node.attributes.insert("fileName".to_string(), image_fn.to_string());
+            },
+            None => {
+                log::error!("image filename `{:?}` not UTF8, skipping!", self.filename);
+                return Err(GlifParserError::GlifNotUtf8)
+            },
         }
         matrix::write!(image_node, self);
         Ok(image_node)
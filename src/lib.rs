@@ -20,6 +20,7 @@ pub mod color; // glif RGBA color for guidelines etc
 pub mod matrix;
 pub mod component;
 pub mod glif;
+pub mod glyphset;
 pub mod guideline;
 #[cfg(feature = "glifimage")]
 pub mod image;
@@ -34,6 +35,7 @@ pub use crate::color::Color;
 pub use crate::component::{FlattenedGlif, GlifComponent, Component, ComponentRect};
 pub use crate::glif::{read, read_pedantic, read_from_filename, read_from_filename_pedantic, write, write_to_filename};
 pub use crate::glif::Glif;
+pub use crate::glyphset::GlyphSet;
 pub use crate::glif::xml;
 #[cfg(feature = "mfek")]
 pub use crate::glif::mfek::MFEKGlif;
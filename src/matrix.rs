@@ -8,6 +8,8 @@ pub use skia::ToSkiaMatrix;
 mod write;
 pub(crate) use self::write::write_matrix as write;
 
+use crate::anchor::Anchor;
+use crate::outline::Contour;
 use crate::point::{Handle, PointData, Point};
 use kurbo::Point as KurboPoint;
 use integer_or_float::IntegerOrFloat;
@@ -43,3 +45,26 @@ impl<PD: PointData> ApplyMatrix for Point<PD> {
         }
     }
 }
+
+impl<PD: PointData> ApplyMatrix for Anchor<PD> {
+    fn apply_matrix(&mut self, matrix: Affine) {
+        let kbp = matrix * KurboPoint::new(self.x as f64, self.y as f64);
+        self.x = kbp.x as f32;
+        self.y = kbp.y as f32;
+    }
+}
+
+impl<PD: PointData> ApplyMatrix for Contour<PD> {
+    fn apply_matrix(&mut self, matrix: Affine) {
+        self.points.apply_matrix(matrix);
+    }
+}
+
+/// Contours and outlines
+impl<T: ApplyMatrix> ApplyMatrix for Vec<T> {
+    fn apply_matrix(&mut self, matrix: Affine) {
+        for it in self.iter_mut() {
+            it.apply_matrix(matrix);
+        }
+    }
+}
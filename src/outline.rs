@@ -1,7 +1,13 @@
 //! .glif `<outline>` and `<contour>`
 
+mod bounds;
+pub use bounds::Bounds;
+#[cfg(feature = "mfek")]
+pub(crate) use bounds::{point_bounds, union as union_bounds};
 pub mod contour;
 pub use contour::Reverse;
+mod direction;
+pub use direction::{CorrectDirection, Direction, DirectionConvention, Winding};
 mod conv;
 pub use conv::{IntoGlifPoints, ToOutline};
 pub use conv::{PenOperations, PenOperationsContour, PenOperationsPath, IntoPenOperations, SplitPenOperations};
@@ -12,6 +18,7 @@ mod quad_to_cubic;
 pub use quad_to_cubic::QuadToCubic;
 mod refigure;
 mod reverse;
+mod segment;
 pub use refigure::*;
 pub mod skia;
 mod xml;
@@ -20,12 +27,155 @@ mod xml;
 use serde::{Serialize, Deserialize};
 
 use crate::component::GlifComponent;
-use crate::point::{GlifPoint, Point};
+use crate::point::{GlifPoint, Point, PointData};
 
-pub type Contour<PD> = Vec<Point<PD>>;
 pub type Outline<PD> = Vec<Contour<PD>>;
 
-pub type GlifContour = Vec<GlifPoint>;
+/// A contour of Skia-friendly [`Point`]s. Traits which only concern the points, like [`Bounds`]
+/// and [`Winding`], are implemented on `Vec<Point<PD>>` (also MFEK's cubic contour), which this
+/// derefs to.
+///
+/// Before v3.0.0 this was an alias of `Vec<Point<PD>>`; `.into()` one to get a contour.
+#[cfg_attr(feature = "glifserde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Contour<PD: PointData> {
+    pub points: Vec<Point<PD>>,
+    /// .glif `<contour identifier>`
+    #[cfg_attr(feature = "glifserde", serde(default))]
+    pub identifier: Option<String>,
+    /// The contour's lib in `public.objectLibs`
+    #[cfg_attr(feature = "glifserde", serde(default))]
+    pub lib: plist::Dictionary,
+    /// How many off-curve points the .glif `<contour>` began with, before its first on-curve
+    /// point. Closed contours are begun at an on-curve point; this many are moved back to the
+    /// front when writing.
+    #[cfg_attr(feature = "glifserde", serde(default))]
+    pub leading_off_curves: usize,
+}
+
+impl<PD: PointData> std::ops::Deref for Contour<PD> {
+    type Target = Vec<Point<PD>>;
+    fn deref(&self) -> &Vec<Point<PD>> {
+        &self.points
+    }
+}
+
+impl<PD: PointData> std::ops::DerefMut for Contour<PD> {
+    fn deref_mut(&mut self) -> &mut Vec<Point<PD>> {
+        &mut self.points
+    }
+}
+
+impl<PD: PointData> Contour<PD> {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn identifier(mut self, identifier: Option<String>) -> Self {
+        self.identifier = identifier;
+        self
+    }
+}
+
+impl<PD: PointData> From<Vec<Point<PD>>> for Contour<PD> {
+    fn from(points: Vec<Point<PD>>) -> Self {
+        Self {
+            points,
+            .. Default::default()
+        }
+    }
+}
+
+/// Compares only the points, as `Vec`'s own comparisons do.
+impl<PD: PointData + PartialEq> PartialEq<Vec<Point<PD>>> for Contour<PD> {
+    fn eq(&self, other: &Vec<Point<PD>>) -> bool {
+        self.points == *other
+    }
+}
+
+impl<PD: PointData + PartialEq, const N: usize> PartialEq<[Point<PD>; N]> for Contour<PD> {
+    fn eq(&self, other: &[Point<PD>; N]) -> bool {
+        self.points == *other
+    }
+}
+
+impl<PD: PointData> FromIterator<Point<PD>> for Contour<PD> {
+    fn from_iter<I: IntoIterator<Item = Point<PD>>>(iter: I) -> Self {
+        Vec::from_iter(iter).into()
+    }
+}
+
+impl<PD: PointData> IntoIterator for Contour<PD> {
+    type Item = Point<PD>;
+    type IntoIter = std::vec::IntoIter<Point<PD>>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.points.into_iter()
+    }
+}
+
+impl<'a, PD: PointData> IntoIterator for &'a Contour<PD> {
+    type Item = &'a Point<PD>;
+    type IntoIter = std::slice::Iter<'a, Point<PD>>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.points.iter()
+    }
+}
+
+impl<'a, PD: PointData> IntoIterator for &'a mut Contour<PD> {
+    type Item = &'a mut Point<PD>;
+    type IntoIter = std::slice::IterMut<'a, Point<PD>>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.points.iter_mut()
+    }
+}
+
+/// A "close to the source" .glif `<contour>`. Before v3.0.0 this was an alias of
+/// `Vec<GlifPoint>`; `.into()` one to get a contour.
+#[cfg_attr(feature = "glifserde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, Default, PartialEq, Hash)]
+pub struct GlifContour {
+    pub points: Vec<GlifPoint>,
+    pub identifier: Option<String>,
+}
+
+impl std::ops::Deref for GlifContour {
+    type Target = Vec<GlifPoint>;
+    fn deref(&self) -> &Vec<GlifPoint> {
+        &self.points
+    }
+}
+
+impl std::ops::DerefMut for GlifContour {
+    fn deref_mut(&mut self) -> &mut Vec<GlifPoint> {
+        &mut self.points
+    }
+}
+
+impl GlifContour {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn identifier(mut self, identifier: Option<String>) -> Self {
+        self.identifier = identifier;
+        self
+    }
+}
+
+impl From<Vec<GlifPoint>> for GlifContour {
+    fn from(points: Vec<GlifPoint>) -> Self {
+        Self {
+            points,
+            .. Default::default()
+        }
+    }
+}
+
+impl FromIterator<GlifPoint> for GlifContour {
+    fn from_iter<I: IntoIterator<Item = GlifPoint>>(iter: I) -> Self {
+        Vec::from_iter(iter).into()
+    }
+}
 
 #[cfg_attr(feature = "glifserde", derive(Serialize, Deserialize))]
 #[derive(Clone, Debug, PartialEq, Hash)]
@@ -0,0 +1,60 @@
+//! Bounding boxes of contours and outlines.
+
+use kurbo::{ParamCurveExtrema as _, Point as KurboPoint, Rect};
+
+use super::segment::{segments, Segment};
+use super::Outline;
+use crate::point::{Handle, Point, PointData};
+
+/// Bounding boxes, as [`kurbo::Rect`]s, or `None` if there are no points.
+pub trait Bounds {
+    /// The smallest box enclosing the curves, found from their extrema.
+    fn bounds(&self) -> Option<Rect>;
+
+    /// The box enclosing every on- and off-curve point: cheaper than [`Bounds::bounds`], and never
+    /// smaller.
+    fn control_bounds(&self) -> Option<Rect>;
+}
+
+/// The union of `rects`, if any.
+pub(crate) fn union(rects: impl IntoIterator<Item = Rect>) -> Option<Rect> {
+    rects.into_iter().reduce(|a, b| a.union(b))
+}
+
+/// The box enclosing `points`, if any.
+pub(crate) fn point_bounds(points: impl IntoIterator<Item = (f32, f32)>) -> Option<Rect> {
+    union(points.into_iter().map(|(x, y)| {
+        let point = KurboPoint::new(x as f64, y as f64);
+        Rect::from_points(point, point)
+    }))
+}
+
+impl<PD: PointData> Bounds for Vec<Point<PD>> {
+    fn bounds(&self) -> Option<Rect> {
+        let on_curve = point_bounds(self.iter().map(|p| (p.x, p.y)));
+        let segments = segments(self).map(|segment| match segment {
+            Segment::Line(line) => line.bounding_box(),
+            Segment::Quad(quad) => quad.bounding_box(),
+            Segment::Cubic(cubic) => cubic.bounding_box(),
+        });
+        union(on_curve.into_iter().chain(segments))
+    }
+
+    fn control_bounds(&self) -> Option<Rect> {
+        let handles = self.iter().flat_map(|p| [p.a, p.b]).filter_map(|h| match h {
+            Handle::At(x, y) => Some((x, y)),
+            Handle::Colocated => None,
+        });
+        point_bounds(self.iter().map(|p| (p.x, p.y)).chain(handles))
+    }
+}
+
+impl<PD: PointData> Bounds for Outline<PD> {
+    fn bounds(&self) -> Option<Rect> {
+        union(self.iter().filter_map(|c| c.bounds()))
+    }
+
+    fn control_bounds(&self) -> Option<Rect> {
+        union(self.iter().filter_map(|c| c.control_bounds()))
+    }
+}
@@ -6,7 +6,7 @@ use kurbo;
 use kurbo::ParamCurveNearest as _;
 
 use crate::error::GlifParserError;
-use crate::point::{Handle, PointData, PointType};
+use crate::point::{Handle, Point, PointData, PointType};
 
 use super::{Contour, GlifContour};
 
@@ -106,7 +106,7 @@ pub trait State {
     }
 }
 
-impl<PD: PointData> State for Contour<PD> {
+impl<PD: PointData> State for Vec<Point<PD>> {
     fn is_open(&self) -> bool {
         match self.len() {
             0 | 1 => true,
@@ -116,17 +116,15 @@ impl<PD: PointData> State for Contour<PD> {
 }
 
 /// Error will always be GlifParserError::PointIdxOutOfBounds
-impl<PD: PointData> PrevNext for Contour<PD> {
+impl<PD: PointData> PrevNext for Vec<Point<PD>> {
     type Error = GlifParserError;
     fn contour_prev_next(&self, idx: usize) -> Result<(Option<usize>, Option<usize>), GlifParserError> {
         let (prev, next) = self.prev_next(idx)?;
         if self.is_open() && self.idx_at_start_or_end(idx)? {
-            let end = self
-                .idx_which_end(idx)?
-                .expect("self.idx_at_start_or_end true but self.idx_which_end returned None???");
-            match end {
-                End::Head => Ok((None, Some(next))),
-                End::Tail => Ok((Some(prev), None)),
+            match self.idx_which_end(idx)? {
+                Some(End::Head) => Ok((None, Some(next))),
+                Some(End::Tail) => Ok((Some(prev), None)),
+                None => Ok((Some(prev), Some(next))),
             }
         } else {
             Ok((Some(self.prev(idx)?), Some(self.next(idx)?)))
@@ -156,7 +154,7 @@ pub trait CheckSmooth {
     fn check_smooth(&mut self, idx: usize) -> Result<bool, GlifParserError>;
 }
 
-impl<PD: PointData> CheckSmooth for Contour<PD> {
+impl<PD: PointData> CheckSmooth for Vec<Point<PD>> {
     fn is_point_smooth_within(&self, idx: usize, within: f32) -> Result<bool, GlifParserError> {
         let end = self.idx_which_end(idx)?;
         let (prev, next) = self.contour_prev_next(idx)?;
@@ -16,8 +16,17 @@ pub trait Reverse: Sized + Clone {
 
 impl<PD: PointData> Reverse for Contour<PD> {
     fn to_reversed(&self) -> Contour<PD> {
-        let mut new_c = Contour::with_capacity(self.len());
-        let open_contour = self.first().unwrap().ptype == PointType::Move && self.len() > 1;
+        Contour { points: self.points.to_reversed(), ..self.clone() }
+    }
+}
+
+impl<PD: PointData> Reverse for Vec<Point<PD>> {
+    fn to_reversed(&self) -> Vec<Point<PD>> {
+        let mut new_c = Vec::with_capacity(self.len());
+        let open_contour = match self.first() {
+            Some(first) => first.ptype == PointType::Move && self.len() > 1,
+            None => return new_c,
+        };
         // This is necessary because although Rev and Chain both implement Iterator, they're
         // incompatible types. So, we need to make a mutable reference to a trait object.
         let (mut iter_t1, mut iter_t2);
@@ -37,28 +46,22 @@ impl<PD: PointData> Reverse for Contour<PD> {
             let b = p.b;
             new_p.a = b;
             new_p.b = a;
+            new_p.a_attributes = p.b_attributes.clone();
+            new_p.b_attributes = p.a_attributes.clone();
             new_c.push(new_p);
         }
 
         let c_len = new_c.len();
+        // A point's type is that of the curve arriving at it, which in a reversed contour is the
+        // one that left it, i.e. arrived at the point now before it.
+        let ptypes: Vec<PointType> = new_c.iter().map(|p| p.ptype).collect();
+        for (i, p) in new_c.iter_mut().enumerate() {
+            p.ptype = ptypes[(i + c_len - 1) % c_len];
+        }
+
         // Considering only open contours post-reversal, reverse which point is the Move.
         if open_contour {
             new_c[0].ptype = PointType::Move;
-            let ptype = if c_len >= 3 {
-                match (new_c[c_len - 2].a, new_c[c_len - 1].b) {
-                    (Handle::At(..), _) | (_, Handle::At(..)) => PointType::Curve,
-                    (Handle::Colocated, Handle::Colocated) => PointType::Line,
-                }
-            } else if c_len == 2 {
-                match (new_c[0].a, new_c[1].b) {
-                    (Handle::At(..), _) | (_, Handle::At(..)) => PointType::Curve,
-                    (Handle::Colocated, Handle::Colocated) => PointType::Line,
-                }
-            } else {
-                log::error!("You probably should not be trying to reverse single-point contours. 気をつけてくださいね。");
-                return new_c
-            };
-            new_c[c_len - 1].ptype = ptype;
             debug_assert!(new_c[0].b == Handle::Colocated);
             debug_assert!(new_c[c_len - 1].a == Handle::Colocated);
         }
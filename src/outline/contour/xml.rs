@@ -8,9 +8,12 @@ impl IntoXML for GlifContour {
     fn xml(&self) -> Element {
         let mut contour_node = Element::new("contour");
         contour_node.children = self
-            .into_iter()
+            .iter()
             .map(|gp| XMLNode::Element(gp.xml()))
             .collect();
+        if let Some(identifier) = &self.identifier {
+            contour_node.attributes.insert("identifier".to_owned(), identifier.clone());
+        }
         contour_node
     }
 }
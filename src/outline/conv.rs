@@ -3,10 +3,11 @@ pub use penops::{IntoPenOperations, PenOperations, SplitPenOperations};
 pub use penops::{PenOperationsContour, PenOperationsPath};
 
 use super::{Contour, GlifContour, Outline};
+use super::create::quadratic_handles;
 
-use crate::point::{GlifPoint, PointData, PointType, WhichHandle};
+use crate::point::{GlifPoint, Handle, PointData, PointType, WhichHandle};
 
-use std::collections::{HashSet, VecDeque};
+use std::collections::VecDeque;
 
 pub trait ToOutline<PD: PointData> {
     fn to_outline(&self) -> Outline<PD>;
@@ -31,43 +32,45 @@ macro_rules! impl_igp {
 impl_igp!(Vec);
 impl_igp!(VecDeque);
 
-pub(crate) fn cleanup_offcurves(contour: &mut Vec<GlifPoint>) {
-    let types: Vec<_> = contour.iter().enumerate().filter_map(|(i, gp)|(gp.ptype != PointType::OffCurve).then(||(i, gp.ptype))).collect();
-    debug_assert!(types.iter().all(|(_, t)|t.is_valid_oncurve()));
-
-    let keep_indices: HashSet<usize> = types.into_iter().map(|(i, pt)| {
-        let i = i as isize;
-        match pt {
-            PointType::Move => vec![i],
-            PointType::Line => vec![i],
-            PointType::QCurve => vec![i-1, i],
-            PointType::Curve => vec![i-2, i-1, i],
-            pt => panic!("PointType {:?} should be impossible when trying to convert back to an XML <outline>", pt),
-        }
-    }).flatten().filter_map(|i| (i >= 0).then(||i.try_into().unwrap())).collect();
-    let keep: Vec<bool> = (0..contour.len()).map(|idx| keep_indices.contains(&idx)).collect();
-    debug_assert_eq!(contour.len(), keep.len());
-    let mut iter = keep.iter();
-    contour.retain(|_|*iter.next().unwrap());
-    debug_assert_eq!(contour.len(), keep_indices.len());
-}
-
 impl<PD: PointData> IntoGlifPoints for Contour<PD> {
     type Output = GlifContour;
+    /// Handles become off-curve points, unless none of a segment's are set, or they're still the
+    /// quadratic curve through a `Curve` point's [`curve_control`](crate::point::Point::curve_control),
+    /// which is written instead. [Implied](crate::point::Point::is_implied) on-curve points are left
+    /// out. Closed contours are written starting from an on-curve point, preceded by their last
+    /// [`leading_off_curves`](Contour::leading_off_curves) off-curve points if there are that many.
     fn into_glifpoints(self) -> Self::Output {
-        let contour_len = self.len();
-        let mut on_points: VecDeque<GlifPoint> = self.iter().map(|p|GlifPoint::from(p)).collect();
-        let mut next_handles: VecDeque<GlifPoint> = self.iter().map(|p|GlifPoint::from_handle(p, WhichHandle::A)).collect();
-        let mut prev_handles: VecDeque<GlifPoint> = self.iter().map(|p|GlifPoint::from_handle(p, WhichHandle::B)).collect();
-        next_handles.rotate_right(1);
-        debug_assert!(on_points.len() == next_handles.len() && next_handles.len() == prev_handles.len());
-        let mut drains = [next_handles.drain(..), prev_handles.drain(..), on_points.drain(..)];
-        let mut glifpoints: Vec<GlifPoint> = std::iter::repeat(()).take(contour_len).fold(Vec::with_capacity(contour_len), |mut acc, _|{acc.extend(drains.iter_mut().map(|d|d.next().unwrap())); acc});
-        debug_assert!(drains.into_iter().all(|mut d|d.next().is_none()));
-        cleanup_offcurves(&mut glifpoints);
-        while let PointType::OffCurve = glifpoints[0].ptype {
-            glifpoints.rotate_right(1);
+        let mut glifpoints: Vec<GlifPoint> = Vec::with_capacity(self.len() * 3);
+        for (i, point) in self.iter().enumerate() {
+            let prev = &self[(i + self.len() - 1) % self.len()];
+            match point.ptype {
+                PointType::Move | PointType::Line => {},
+                PointType::QCurve => if let Handle::At(..) = point.b {
+                    // In a reversed contour, the off-curve point's attributes are on the `a` before
+                    let attributes = if point.b_attributes.is_empty() { &prev.a_attributes } else { &point.b_attributes };
+                    glifpoints.push(GlifPoint::from_handle(point, WhichHandle::B).attributes(attributes));
+                },
+                PointType::Curve if point.curve_control.is_some_and(|c| (prev.a, point.b) == quadratic_handles((prev.x, prev.y), c, (point.x, point.y))) => {
+                    let (x, y) = point.curve_control.unwrap();
+                    glifpoints.push(GlifPoint::from_x_y_type((x, y), PointType::OffCurve).attributes(&prev.a_attributes));
+                },
+                PointType::Curve => if (prev.a, point.b) != (Handle::Colocated, Handle::Colocated) {
+                    glifpoints.push(GlifPoint::from_handle(prev, WhichHandle::A));
+                    glifpoints.push(GlifPoint::from_handle(point, WhichHandle::B));
+                },
+                pt => log::error!("PointType {:?} can't be written to an XML <outline>, writing it without handles", pt),
+            }
+            if !point.is_implied() {
+                glifpoints.push(GlifPoint::from(point));
+            }
+        }
+        if let Some(start) = glifpoints.iter().position(|gp| gp.ptype != PointType::OffCurve) {
+            glifpoints.rotate_left(start);
+            let leading = self.leading_off_curves;
+            if leading < glifpoints.len() && glifpoints[glifpoints.len() - leading..].iter().all(|gp| gp.ptype == PointType::OffCurve) {
+                glifpoints.rotate_right(leading);
+            }
         }
-        glifpoints
+        GlifContour::from(glifpoints).identifier(self.identifier)
     }
 }
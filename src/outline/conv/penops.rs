@@ -4,7 +4,7 @@ use crate::outline::contour::{PrevNext as _, State as _};
 use crate::outline::refigure::RefigurePointTypes as _;
 use crate::outline::{Contour, Outline};
 use crate::point::IsValid;
-use crate::point::{GlifPoint, Handle, Point, PointData, PointType, WhichHandle};
+use crate::point::{GlifPoint, Handle, HandleAttributes, Point, PointData, PointType, WhichHandle};
 use PointType::*;
 
 /// Representation of glyph data as pen operations, rather than as [`Point`]'s.
@@ -87,7 +87,7 @@ pub trait IntoPenOperations {
     fn into_pen_operations(&mut self) -> Result<Vec<PenOperations>, GlifParserError>;
 }
 
-impl<PD: PointData> IntoPenOperations for Contour<PD> {
+impl<PD: PointData> IntoPenOperations for Vec<Point<PD>> {
     // mutable as it triggers a refigure!
     fn into_pen_operations(&mut self) -> Result<Vec<PenOperations>, GlifParserError> {
         let is_closed = self.is_closed();
@@ -95,7 +95,10 @@ impl<PD: PointData> IntoPenOperations for Contour<PD> {
         let mut pen_vec = vec![];
 
         if is_closed {
-            pen_vec.push(PenOperations::MoveTo(self.first().unwrap().into()));
+            match self.first() {
+                Some(first) => pen_vec.push(PenOperations::MoveTo(first.into())),
+                None => return Ok(pen_vec),
+            }
         }
 
         for (pi, point) in self.iter().enumerate() {
@@ -103,7 +106,7 @@ impl<PD: PointData> IntoPenOperations for Contour<PD> {
                 PointType::Move => PenOperations::MoveTo(point.into()),
                 PointType::Line => {
                     if pi == 1 {
-                        pen_vec.pop().unwrap();
+                        pen_vec.pop();
                         PenOperations::LineTo(point.into())
                     } else {
                         PenOperations::LineTo(point.into())
@@ -112,7 +115,7 @@ impl<PD: PointData> IntoPenOperations for Contour<PD> {
                 PointType::QCurve => {
                     PenOperations::QuadTo(GlifPoint::from_handle(&point, WhichHandle::A), point.into())
                 }
-                PointType::Curve => match self.contour_prev_next(pi).unwrap() {
+                PointType::Curve => match self.contour_prev_next(pi)? {
                     (_, Some(next)) => PenOperations::CurveTo(
                         GlifPoint::from_handle(&point, WhichHandle::A),
                         GlifPoint::from_handle(&self[next], WhichHandle::B),
@@ -123,7 +126,7 @@ impl<PD: PointData> IntoPenOperations for Contour<PD> {
                         GlifPoint::from_handle(&point, WhichHandle::B),
                         point.into(),
                     ),
-                    (None, None) => unreachable!(),
+                    (None, None) => return Err(GlifParserError::ContourLenOneUnexpected),
                 },
                 ptype => return Err(GlifParserError::GlifContourHasBadPointType { pi, ptype }),
             };
@@ -131,11 +134,11 @@ impl<PD: PointData> IntoPenOperations for Contour<PD> {
         }
 
         if is_closed {
-            if self.last().unwrap().ptype == Curve && self.first().unwrap().ptype == Curve {
-                let lp = pen_vec.last().unwrap().clone();
-                if let PenOperations::CurveTo(p1, p2, _p3) = lp {
-                    *pen_vec.last_mut().unwrap() =
-                        PenOperations::CurveTo(p1, p2, self.first().unwrap().into());
+            if let (Some(first), Some(last)) = (self.first(), self.last()) {
+                if last.ptype == Curve && first.ptype == Curve {
+                    if let Some(PenOperations::CurveTo(_, _, p3)) = pen_vec.last_mut() {
+                        *p3 = first.into();
+                    }
                 }
             }
             pen_vec.push(PenOperations::Close);
@@ -221,10 +224,16 @@ impl<PD: PointData> ToOutline<PD> for PenOperationsPath {
 
                 let mut point = Point::<PD> {
                     name: None,
+                    identifier: None,
+                    lib: None,
+                    a_attributes: HandleAttributes::default(),
+                    b_attributes: HandleAttributes::default(),
                     data: None,
                     x: points[0].x.into(),
                     y: points[0].y.into(),
                     smooth: false,
+                    implied: false,
+                    curve_control: None,
                     // These will be fixed below, if needed
                     a: Handle::Colocated,
                     b: Handle::Colocated,
@@ -251,18 +260,25 @@ impl<PD: PointData> ToOutline<PD> for PenOperationsPath {
                             point.a = Handle::At(next_points[2].x.into(), next_points[2].y.into());
                         }
                     }
-                    _ => unreachable!("Got an illegal point type {:?}", point.ptype),
+                    PointType::QCurve => {
+                        if next_points.len() == 2 {
+                            point.a = Handle::At(next_points[1].x.into(), next_points[1].y.into());
+                        }
+                        if let Some(p) = points.get(1) {
+                            point.b = Handle::At(p.x.into(), p.y.into());
+                        }
+                    }
+                    ptype => {
+                        log::warn!("Skipped pen operation with illegal point type {:?}", ptype);
+                        continue;
+                    }
                 }
                 contour.push(point);
             }
 
-            if contour.len() > 1 {
-                let first = contour.first().unwrap();
-                let last = contour.last().unwrap();
-                let (x, y, _a, b) = (last.x, last.y, last.a, last.b);
-                if x == first.x && y == first.y {
-                    contour.pop().unwrap();
-                    contour.first_mut().unwrap().b = b;
+            if contour.len() > 1 && contour[0].x == contour[contour.len() - 1].x && contour[0].y == contour[contour.len() - 1].y {
+                if let Some(last) = contour.pop() {
+                    contour[0].b = last.b;
                 }
             }
             ret.push(contour);
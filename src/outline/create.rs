@@ -1,183 +1,163 @@
-use std::collections::VecDeque;
-
 use super::{Contour, GlifContour, GlifOutline, Outline};
-use crate::point::{GlifPoint, Handle, Point, PointData, PointType};
+use crate::error::GlifParserError::{self, GlifInputError};
+use crate::point::{GlifPoint, Handle, HandleAttributes, Point, PointData, PointType};
 
-use integer_or_float::IntegerOrFloat;
 use log::warn;
 
-fn midpoint(
-    x1: IntegerOrFloat,
-    x2: IntegerOrFloat,
-    y1: IntegerOrFloat,
-    y2: IntegerOrFloat,
-) -> (IntegerOrFloat, IntegerOrFloat) {
-    ((x1 + x2) / 2., (y1 + y2) / 2.)
+fn handle(gp: &GlifPoint) -> Handle {
+    Handle::At(gp.x.into(), gp.y.into())
 }
 
-pub fn quadratic_contour<PD: PointData>(gc: &GlifContour) -> Contour<PD> {
-    let mut contour: Contour<PD> = Vec::new();
-    let mut stack: VecDeque<&GlifPoint> = VecDeque::new();
-
-    for gp in gc.iter() {
-        match gp.ptype {
-            PointType::OffCurve => {
-                stack.push_back(gp);
-            }
-            _ => {}
-        }
+/// The on-curve point the GLIF spec implies midway between two consecutive quadratic off-curve
+/// points, whose handles are those off-curve points.
+fn implied_point<PD: PointData>(b: &GlifPoint, a: &GlifPoint) -> Point<PD> {
+    let (bx, by, ax, ay): (f32, f32, f32, f32) = (b.x.into(), b.y.into(), a.x.into(), a.y.into());
+    Point {
+        x: (bx + ax) / 2.,
+        y: (by + ay) / 2.,
+        a: handle(a),
+        b: handle(b),
+        b_attributes: HandleAttributes::from(b),
+        ptype: PointType::QCurve,
+        smooth: true,
+        implied: true,
+        ..Default::default()
+    }
+}
 
-        if stack.len() == 2 {
-            let h1 = stack.pop_front().unwrap();
-            let h2 = stack.pop_front().unwrap();
-            let mp = midpoint(h1.x, h2.x, h1.y, h2.y);
-
-            contour.push(Point {
-                x: h1.x.into(),
-                y: h1.y.into(),
-                ptype: h1.ptype,
-                name: h1.name.clone(),
-                smooth: h1.smooth,
-                ..Default::default()
-            });
-
-            contour.push(Point {
-                x: mp.0.into(),
-                y: mp.1.into(),
-                ptype: PointType::QCurve,
-                name: gp.name.clone(),
-                ..Default::default()
-            });
-
-            stack.push_back(h2);
-        } else if gp.ptype != PointType::OffCurve {
-            let h1 = stack.pop_front();
-            match h1 {
-                Some(h) => contour.push(Point {
-                    x: h.x.into(),
-                    y: h.y.into(),
-                    ptype: h.ptype,
-                    name: h.name.clone(),
-                    smooth: h.smooth,
-                    ..Default::default()
-                }),
-                _ => {}
-            }
+/// The handles of the cubic curve from `from` to `to` which is the quadratic curve through
+/// `control`.
+pub(super) fn quadratic_handles(from: (f32, f32), control: (f32, f32), to: (f32, f32)) -> (Handle, Handle) {
+    let third = |p: (f32, f32)| Handle::At(p.0 + (control.0 - p.0) * 2. / 3., p.1 + (control.1 - p.1) * 2. / 3.);
+    (third(from), third(to))
+}
 
-            contour.push(Point {
-                x: gp.x.into(),
-                y: gp.y.into(),
-                ptype: gp.ptype,
-                name: gp.name.clone(),
-                smooth: gp.smooth,
-                ..Default::default()
-            });
+/// Join the last point of `contour` to `to` through `offcurves`. When `closing`, `to` is already
+/// the first point of `contour`.
+fn push_segment<PD: PointData>(contour: &mut Contour<PD>, offcurves: &[&GlifPoint], to: &GlifPoint, closing: bool) -> Result<(), GlifParserError> {
+    let (mut b, mut b_attributes) = (Handle::Colocated, HandleAttributes::default());
+    let mut curve_control = None;
+    let set_a = |contour: &mut Contour<PD>, a: &GlifPoint, a_attributes: HandleAttributes| {
+        if let Some(last) = contour.last_mut() {
+            last.a = handle(a);
+            last.a_attributes = a_attributes;
         }
+    };
+
+    match (to.ptype, offcurves) {
+        (PointType::Line | PointType::Move | PointType::Curve | PointType::QCurve, []) => {},
+        (PointType::Line | PointType::Move, _) => {
+            return Err(GlifInputError(format!("{} point preceded by off-curve points", to.ptype)))
+        },
+        // A quadratic curve, kept as the same curve as a cubic
+        (PointType::Curve, [control]) => if let Some(last) = contour.last_mut() {
+            let control_xy = (control.x.into(), control.y.into());
+            let a;
+            (a, b) = quadratic_handles((last.x, last.y), control_xy, (to.x.into(), to.y.into()));
+            last.a = a;
+            last.a_attributes = HandleAttributes::from(*control);
+            curve_control = Some(control_xy);
+        },
+        (PointType::Curve, [h1, h2]) => {
+            set_a(contour, h1, HandleAttributes::from(*h1));
+            (b, b_attributes) = (handle(h2), HandleAttributes::from(*h2));
+        },
+        (PointType::Curve, _) => {
+            return Err(GlifInputError(format!("curve point preceded by {} off-curve points, more than two", offcurves.len())))
+        },
+        (PointType::QCurve, [first, rest @ ..]) => {
+            // Each quadratic off-curve point's attributes are kept on the `b` it becomes
+            set_a(contour, first, HandleAttributes::default());
+            let mut control = *first;
+            for next in rest {
+                contour.push(implied_point(control, next));
+                control = next;
+            }
+            (b, b_attributes) = (handle(control), HandleAttributes::from(control));
+        },
+        (ptype, _) => return Err(GlifInputError(format!("{} point in contour", ptype))),
     }
 
-    if let Some(h1) = stack.pop_front() {
-        if let Some(h2) = contour.get(0) {
-            let mp = midpoint(h1.x.into(), h2.x.into(), h1.y.into(), h2.y.into());
-            let ptype = if h2.ptype == PointType::OffCurve {
-                PointType::QCurve
-            } else {
-                PointType::QClose
-            };
-
-            contour.push(Point {
-                x: h1.x.into(),
-                y: h1.y.into(),
-                ptype: h1.ptype,
-                name: h1.name.clone(),
-                smooth: h1.smooth,
-                ..Default::default()
-            });
-
-            contour.push(Point {
-                x: mp.0.into(),
-                y: mp.1.into(),
-                ptype,
-                ..Default::default()
-            });
-        }
+    if closing {
+        contour[0].b = b;
+        contour[0].b_attributes = b_attributes;
+        contour[0].curve_control = curve_control;
+    } else {
+        contour.push(Point { b, b_attributes, curve_control, ..Point::from(to) });
     }
-
-    contour
+    Ok(())
 }
 
-pub fn cubic_contour<PD: PointData>(gc: &GlifContour) -> Contour<PD> {
-    let mut contour: Contour<PD> = Vec::new();
-    let mut stack: VecDeque<&GlifPoint> = VecDeque::new();
-
-    for gp in gc.iter() {
+/// Convert a .glif `<contour>` of any mix of `move`, `line`, `curve` and `qcurve` points. Off-curve
+/// points become the handles of the on-curve points they lie between, and on-curve points implied
+/// by quadratic off-curves (including in contours with no on-curve points at all) are added, marked
+/// [`Point::implied`] so that they're left out again when writing.
+///
+/// Closed contours are begun at their first on-curve point; the number of off-curve points before
+/// it is kept in [`Contour::leading_off_curves`].
+pub fn contour<PD: PointData>(gc: &GlifContour) -> Result<Contour<PD>, GlifParserError> {
+    for (pi, gp) in gc.iter().enumerate() {
         match gp.ptype {
-            PointType::OffCurve => {
-                stack.push_back(&gp);
-            }
-            PointType::Line | PointType::Move | PointType::Curve => {
-                let h1 = stack.pop_front();
-                let h2 = stack.pop_front();
-
-                contour.last_mut().map(|p| p.a = Handle::from(h1));
-
-                let (x, y) = (gp.x.into(), gp.y.into());
-
-                contour.push(Point {
-                    x,
-                    y,
-                    a: Handle::from(h1),
-                    b: Handle::from(h2),
-                    smooth: gp.smooth,
-                    name: gp.name.clone(),
-                    ptype: gp.ptype,
-                    ..Default::default()
-                });
-            }
-            PointType::QCurve => {
-                unreachable!("Quadratic point in cubic glyph! File is corrupt.")
-            }
-            _ => {}
+            PointType::Move if pi != 0 => return Err(GlifInputError(String::from("move point not first in its contour"))),
+            PointType::Move | PointType::Line | PointType::Curve | PointType::QCurve | PointType::OffCurve => {},
+            ptype => return Err(GlifParserError::GlifContourHasBadPointType { pi, ptype }),
         }
     }
 
-    let h1 = stack.pop_front();
-    let h2 = stack.pop_front();
-
-    contour.last_mut().map(|p| p.a = Handle::from(h1));
-
-    if contour.len() > 0 && contour[0].ptype != PointType::Move {
-        contour.first_mut().map(|p| p.b = Handle::from(h2));
+    let Some(start) = gc.iter().position(|gp| gp.ptype != PointType::OffCurve) else {
+        // A TrueType-style closed contour of nothing but quadratic off-curve points
+        let points: Vec<_> = gc.iter().zip(gc.iter().cycle().skip(1)).map(|(b, a)| implied_point(b, a)).collect();
+        return Ok(Contour::from(points).identifier(gc.identifier.clone()))
+    };
+
+    let mut contour = Contour::from(Vec::with_capacity(gc.len())).identifier(gc.identifier.clone());
+    let mut offcurves: Vec<&GlifPoint> = vec![];
+    for gp in gc[start..].iter().chain(gc[..start].iter()) {
+        if gp.ptype == PointType::OffCurve {
+            offcurves.push(gp);
+        } else if contour.is_empty() {
+            contour.push(Point::from(gp));
+        } else {
+            push_segment(&mut contour, &offcurves, gp, false)?;
+            offcurves.clear();
+        }
     }
 
-    if contour.len() == 1 && contour.first().unwrap().ptype == PointType::Move {
-        warn!(
-            "Dropped empty contour. Lone `move` point in .glif? GlifContour: {:?}",
-            &gc
-        );
-    } else if contour.len() > 0 {
-        return contour;
+    if contour[0].ptype == PointType::Move {
+        if !offcurves.is_empty() {
+            return Err(GlifInputError(String::from("open contour ends with off-curve points")))
+        }
+        if contour.len() == 1 {
+            warn!("Dropped empty contour. Lone `move` point in .glif? GlifContour: {:?}", &gc);
+            return Ok(Contour::new())
+        }
+    } else {
+        push_segment(&mut contour, &offcurves, &gc[start], true)?;
+        contour.leading_off_curves = start;
     }
 
-    // In case of empty or single move point contours, return an empty contour
-    Vec::new()
+    Ok(contour)
 }
 
-impl<PD: PointData> Into<Outline<PD>> for GlifOutline {
-    fn into(self) -> Outline<PD> {
+impl<PD: PointData> TryFrom<GlifOutline> for Outline<PD> {
+    type Error = GlifParserError;
+
+    /// Contours which convert to nothing are dropped.
+    fn try_from(goutline: GlifOutline) -> Result<Self, Self::Error> {
         let mut outline: Outline<PD> = Vec::new();
 
-        for gc in self.contours.iter() {
-            let contour: Contour<PD> = match gc[0].ptype {
-                PointType::Curve | PointType::Line | PointType::Move => cubic_contour(gc),
-                PointType::QCurve | PointType::QClose => quadratic_contour(gc),
-                _ => Vec::new(), // Handle other cases as necessary
-            };
+        for (ci, gc) in goutline.contours.into_iter().enumerate() {
+            let contour: Contour<PD> = contour(&gc).map_err(|e| match e {
+                GlifParserError::GlifContourHasBadPointType { pi, ptype } => GlifParserError::GlifOutlineHasBadPointType { ci, pi, ptype },
+                GlifInputError(s) => GlifInputError(format!("Contour {}: {}", ci, s)),
+                e => e,
+            })?;
 
             if !contour.is_empty() {
                 outline.push(contour);
             }
         }
 
-        outline
+        Ok(outline)
     }
 }
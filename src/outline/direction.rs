@@ -0,0 +1,117 @@
+//! Signed area and direction of contours, and correcting outlines' contour directions.
+
+use kurbo::{ParamCurveArea as _, Shape as _};
+
+use super::contour::State as _;
+use super::segment::{closed_path, segments, Segment};
+use super::{Outline, Reverse as _};
+use crate::point::{Point, PointData};
+
+/// Which way round a contour goes, in the y-up coordinates of .glif files.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Direction {
+    Clockwise,
+    CounterClockwise,
+}
+
+/// Which way outer contours go, counters and the contours inside them alternating.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum DirectionConvention {
+    /// Outer contours counter-clockwise, as the UFO spec and CFF outlines expect
+    #[default]
+    PostScript,
+    /// Outer contours clockwise, as in TrueType `glyf` outlines
+    TrueType,
+}
+
+impl DirectionConvention {
+    /// The direction of contours inside `depth` others.
+    pub fn direction_at_depth(&self, depth: usize) -> Direction {
+        match (self, depth % 2) {
+            (Self::PostScript, 0) | (Self::TrueType, 1) => Direction::CounterClockwise,
+            _ => Direction::Clockwise,
+        }
+    }
+}
+
+pub trait Winding {
+    /// The area the contour encloses, positive if it's counter-clockwise and negative if it's
+    /// clockwise. Open contours are taken as closed by a straight line.
+    fn signed_area(&self) -> f64;
+
+    /// `None` if the contour encloses no area.
+    fn direction(&self) -> Option<Direction> {
+        let area = self.signed_area();
+        if area > 0. {
+            Some(Direction::CounterClockwise)
+        } else if area < 0. {
+            Some(Direction::Clockwise)
+        } else {
+            None
+        }
+    }
+
+    fn is_clockwise(&self) -> bool {
+        self.direction() == Some(Direction::Clockwise)
+    }
+
+    fn is_counter_clockwise(&self) -> bool {
+        self.direction() == Some(Direction::CounterClockwise)
+    }
+}
+
+impl<PD: PointData> Winding for Vec<Point<PD>> {
+    fn signed_area(&self) -> f64 {
+        let curves: f64 = segments(self)
+            .map(|segment| match segment {
+                Segment::Line(line) => line.signed_area(),
+                Segment::Quad(quad) => quad.signed_area(),
+                Segment::Cubic(cubic) => cubic.signed_area(),
+            })
+            .sum();
+        let closing = match (self.is_open(), self.first(), self.last()) {
+            (true, Some(first), Some(last)) => (last.x as f64 * first.y as f64 - first.x as f64 * last.y as f64) / 2.,
+            _ => 0.,
+        };
+        curves + closing
+    }
+}
+
+pub trait CorrectDirection {
+    /// Reverse contours as needed for outer contours to follow `convention`, with each contour
+    /// inside others going the other way to the one it's directly inside. A contour is inside
+    /// another if it's smaller and most of its on-curve points are within it. Open contours, and
+    /// those enclosing no area, are left alone.
+    ///
+    /// Reversal keeps each point's data, name, identifier and smoothness.
+    fn correct_direction(&mut self, convention: DirectionConvention);
+}
+
+impl<PD: PointData> CorrectDirection for Outline<PD> {
+    fn correct_direction(&mut self, convention: DirectionConvention) {
+        let areas: Vec<Option<f64>> = self
+            .iter()
+            .map(|c| Some(c.signed_area()).filter(|area| c.is_closed() && *area != 0.))
+            .collect();
+        let paths: Vec<_> = self.iter().map(|c| closed_path(c)).collect();
+
+        let depths: Vec<usize> = (0..self.len())
+            .map(|i| {
+                let Some(area) = areas[i] else { return 0 };
+                (0..self.len())
+                    .filter(|&j| j != i && areas[j].map(|outer| outer.abs() > area.abs()).unwrap_or(false))
+                    .filter(|&j| {
+                        let inside = self[i].iter().filter(|p| paths[j].winding((p.x as f64, p.y as f64).into()) != 0).count();
+                        inside * 2 > self[i].len()
+                    })
+                    .count()
+            })
+            .collect();
+
+        for ((contour, area), depth) in self.iter_mut().zip(areas).zip(depths) {
+            if area.is_some() && contour.direction() != Some(convention.direction_at_depth(depth)) {
+                contour.reverse();
+            }
+        }
+    }
+}
@@ -7,7 +7,7 @@ use super::{
 };
 use crate::error::GlifParserError;
 use crate::point::PointLike as _;
-use crate::point::{GlifPoint, PointData, PointType, PointType::*};
+use crate::point::{GlifPoint, Point, PointData, PointType, PointType::*};
 
 use std::iter::Iterator;
 
@@ -109,7 +109,7 @@ impl<PD: PointData> IntoKurbo for Outline<PD> {
 }
 
 use crate::outline::conv::IntoPenOperations;
-impl<PD: PointData> IntoKurbo for Contour<PD> {
+impl<PD: PointData> IntoKurbo for Vec<Point<PD>> {
     fn into_kurbo_vec(mut self) -> Result<Vec<PathEl>, GlifParserError> {
         let ret = self
             .into_pen_operations()?
@@ -121,6 +121,12 @@ impl<PD: PointData> IntoKurbo for Contour<PD> {
     }
 }
 
+impl<PD: PointData> IntoKurbo for Contour<PD> {
+    fn into_kurbo_vec(self) -> Result<Vec<PathEl>, GlifParserError> {
+        self.points.into_kurbo_vec()
+    }
+}
+
 /// [`kurbo::BezPath`] to type (most useful for [`Outline`])
 pub trait FromKurbo {
     fn from_kurbo(kpath: &BezPath) -> Self;
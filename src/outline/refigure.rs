@@ -22,7 +22,7 @@ impl<PD: PointData> RefigurePointTypes<PD> for Outline<PD> {
     }
 }
 
-impl<PD: PointData> RefigurePointTypes<PD> for Contour<PD> {
+impl<PD: PointData> RefigurePointTypes<PD> for Vec<Point<PD>> {
     fn refigure_point_types(&mut self) {
         for i in 0..self.len() {
             if let Handle::At(ax, ay) = self[i].a {
@@ -44,7 +44,7 @@ trait PointTypeForIdx: ContourPrevNext + ContourState {
     fn point_type_for_idx(&self, idx: usize) -> PointType;
 }
 
-impl<PD: PointData> PointTypeForIdx for Contour<PD> {
+impl<PD: PointData> PointTypeForIdx for Vec<Point<PD>> {
     fn point_type_for_idx(&self, idx: usize) -> PointType {
         let open_contour = self.is_open();
         let point = &self[idx];
@@ -97,6 +97,12 @@ impl RoundToInt for GlifPoint {
     }
 }
 
+impl<PD: PointData> RoundToInt for Contour<PD> {
+    fn round_to_int(&mut self) {
+        self.points.round_to_int();
+    }
+}
+
 macro_rules! impl_rti {
     ($type:ident) => {
         impl<R: RoundToInt> RoundToInt for $type<R> {
@@ -0,0 +1,54 @@
+//! The curves making up a [`Contour`], for geometry which needs them whole.
+
+use kurbo::{BezPath, CubicBez, Line, PathEl, Point as KurboPoint, QuadBez};
+
+use crate::point::{Handle, Point, PointData, PointType};
+
+pub(super) enum Segment {
+    Line(Line),
+    Quad(QuadBez),
+    Cubic(CubicBez),
+}
+
+/// The curves of `contour`: from each point to the next, by way of the first's `a` handle and the
+/// second's `b`, or a single off-curve point if the second is a `QCurve`.
+pub(super) fn segments<PD: PointData>(contour: &[Point<PD>]) -> impl Iterator<Item = Segment> + '_ {
+    let closed = contour.first().map(|p| p.ptype != PointType::Move).unwrap_or(false);
+    let ends = contour.len().saturating_sub(if closed { 0 } else { 1 });
+    (0..ends).map(move |i| {
+        let (from, to) = (&contour[i], &contour[(i + 1) % contour.len()]);
+        let at = |p: &Point<PD>, handle: Handle| match handle {
+            Handle::At(x, y) => KurboPoint::new(x as f64, y as f64),
+            Handle::Colocated => KurboPoint::new(p.x as f64, p.y as f64),
+        };
+        let (p0, p3) = (at(from, Handle::Colocated), at(to, Handle::Colocated));
+        match (from.a, to.b) {
+            (Handle::Colocated, Handle::Colocated) => Segment::Line(Line::new(p0, p3)),
+            (_, b) if to.ptype == PointType::QCurve && b != Handle::Colocated => Segment::Quad(QuadBez::new(p0, at(to, b), p3)),
+            (a, b) => Segment::Cubic(CubicBez::new(p0, at(from, a), at(to, b), p3)),
+        }
+    })
+}
+
+impl Segment {
+    pub(super) fn path_el(&self) -> PathEl {
+        match self {
+            Segment::Line(line) => PathEl::LineTo(line.p1),
+            Segment::Quad(quad) => PathEl::QuadTo(quad.p1, quad.p2),
+            Segment::Cubic(cubic) => PathEl::CurveTo(cubic.p1, cubic.p2, cubic.p3),
+        }
+    }
+}
+
+/// `contour` as a closed [`BezPath`], closed by a straight line if it's open.
+pub(super) fn closed_path<PD: PointData>(contour: &[Point<PD>]) -> BezPath {
+    let mut path = BezPath::new();
+    if let Some(first) = contour.first() {
+        path.move_to((first.x as f64, first.y as f64));
+        for segment in segments(contour) {
+            path.push(segment.path_el());
+        }
+        path.close_path();
+    }
+    path
+}
@@ -2,7 +2,7 @@ use skia_safe as skia;
 use skia::{Point as SkPoint, Path as SkPath};
 use skia::path::{Verb as SkVerb};
 
-use crate::point::{Handle, Point, PointData, PointType};
+use crate::point::{Handle, HandleAttributes, Point, PointData, PointType};
 use crate::{Contour, Outline};
 
 use crate::outline::QuadToCubic;
@@ -188,10 +188,16 @@ impl<PD: PointData> FromSkOutline for Outline<PD> {
 
                 let mut point = Point::<PD> {
                     name: None,
+                    identifier: None,
+                    lib: None,
+                    a_attributes: HandleAttributes::default(),
+                    b_attributes: HandleAttributes::default(),
                     data: None,
                     x: points[0].x,
                     y: points[0].y,
                     smooth: false,
+                    implied: false,
+                    curve_control: None,
                     // These will be fixed below, if needed
                     a: Handle::Colocated,
                     b: Handle::Colocated,
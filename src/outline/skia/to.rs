@@ -1,7 +1,7 @@
 use skia_safe as skia;
 
 use crate::point::{Point, PointData, PointType, WhichHandle};
-use crate::{Contour, Outline};
+use crate::Outline;
 
 /// glifparser returns for you two Skia paths when called on an outline, because it is extremely
 /// liekly that you are going to want to draw open paths in a different way than you draw closed
@@ -92,7 +92,7 @@ impl<PD: PointData> ToSkiaPaths for Outline<PD> {
     }
 }
 
-impl<PD: PointData> ToSkiaPath for Contour<PD> {
+impl<PD: PointData> ToSkiaPath for Vec<Point<PD>> {
     fn to_skia_path(&self, spt: Option<SkiaPointTransforms>) -> Option<skia::Path> {
         if self.is_empty() {
             return None;
@@ -9,8 +9,14 @@ pub struct Pedantry {
 }
 
 impl Pedantry {
+    /// Whether input which falls short of an OpenType or TrueType level may be mended to meet it.
     pub fn should_mend(&self) -> bool {
-        !(self.level.is_sfnt() && self.mend.is_never())
+        !self.level.is_sfnt() || self.mend.meets_level()
+    }
+
+    /// Whether `f`, of class `fc`, is acceptable as is, or may be rounded to become so.
+    pub fn allows(&self, f: IntegerOrFloat, fc: FloatClass) -> bool {
+        f32::from(f).fract() == 0. || !self.level.requires_integer(fc) || self.mend.meets_level()
     }
 }
 
@@ -18,12 +24,37 @@ impl Pedantry {
 #[derivative(Default)]
 pub enum Mend {
     #[derivative(Default(new="true"))]
+    /// Fix whatever we can: spec errors, outdated input, and input that falls short of the [`Level`]
     Always,
+    /// Reject anything we'd otherwise fix
     Never,
+    /// Fix input which breaks the UFO spec, but don't upgrade outdated input or fix what falls
+    /// short of the [`Level`]
     UfoSpecErrorsOnly,
+    /// Upgrade input written to an outdated version of the UFO spec, but fix nothing else
     UfoSpecOutdatedOnly,
 }
 
+impl Mend {
+    /// Whether input which breaks the spec (e.g. duplicate identifiers) may be fixed, rather than
+    /// rejected.
+    pub fn fixes_errors(&self) -> bool {
+        self.is_always() || self.is_ufo_spec_errors_only()
+    }
+
+    /// Whether input written to an outdated version of the spec (e.g. GLIF format 1) may be
+    /// upgraded, rather than rejected.
+    pub fn upgrades_outdated(&self) -> bool {
+        self.is_always() || self.is_ufo_spec_outdated_only()
+    }
+
+    /// Whether input which is good UFO, but falls short of the [`Level`] (e.g. float point
+    /// locations when compiling TrueType), may be brought up to it, rather than rejected.
+    pub fn meets_level(&self) -> bool {
+        self.is_always()
+    }
+}
+
 #[derive(Derivative, Debug, Copy, Clone, PartialEq, Eq, IsVariant, Unwrap)]
 #[derivative(Default)]
 pub enum Level {
@@ -42,21 +73,34 @@ pub enum Level {
     TrueType,
 }
 
+/// Kinds of number in a .glif which some [`Level`]s require to be integers.
 #[derive(Debug, Copy, Clone, PartialEq, Eq, IsVariant, Unwrap)]
 pub enum FloatClass {
     Anchor,
-    AdvanceWidth
+    /// `<advance>` width or height
+    AdvanceWidth,
+    /// `<point>` x and y
+    Point,
+    /// `<component>` xOffset and yOffset
+    ComponentOffset,
+    /// `<guideline>` x and y
+    Guideline,
 }
 
-impl FloatClass {
-    fn should_round(&self) -> bool {
-        *self == FloatClass::Anchor
+impl Level {
+    /// Whether numbers of class `fc` must be integers at this level. OpenType requires integer
+    /// anchors and advances; TrueType requires everything but transformation scales and
+    /// guideline angles to be integers.
+    pub fn requires_integer(&self, fc: FloatClass) -> bool {
+        match fc {
+            FloatClass::Anchor | FloatClass::AdvanceWidth => self.is_sfnt(),
+            FloatClass::Point | FloatClass::ComponentOffset | FloatClass::Guideline => self.is_true_type(),
+        }
     }
-}
 
-impl Level {
+    /// `f`, rounded if this level requires numbers of class `fc` to be integers.
     pub fn maybe_round(&self, f: IntegerOrFloat, fc: FloatClass) -> f32 {
-        if self.is_sfnt() && fc.should_round() {
+        if self.requires_integer(fc) {
             f32::from(f).round()
         } else {
             f.into()
@@ -12,7 +12,7 @@ use std::str::FromStr;
 use integer_or_float::IntegerOrFloat;
 
 #[cfg(feature = "glifserde")]
-use serde::{Serialize, Deserialize};
+use serde::{de::DeserializeOwned, Serialize, Deserialize};
 
 /// A "close to the source" .glif `<point>`
 #[cfg_attr(feature = "glifserde", derive(Serialize, Deserialize))]
@@ -23,6 +23,7 @@ pub struct GlifPoint {
     pub smooth: bool,
     pub name: Option<String>,
     pub ptype: PointType,
+    pub identifier: Option<String>,
 }
 
 impl GlifPoint {
@@ -36,6 +37,18 @@ impl GlifPoint {
         self.name = name;
         self
     }
+
+    pub fn identifier(mut self, identifier: Option<String>) -> Self {
+        self.identifier = identifier;
+        self
+    }
+
+    /// Give an off-curve point the attributes of the handle it was made from
+    pub fn attributes(mut self, attributes: &HandleAttributes) -> Self {
+        self.name = attributes.name.clone();
+        self.identifier = attributes.identifier.clone();
+        self
+    }
 }
 
 impl GlifPoint {
@@ -70,6 +83,36 @@ pub enum Handle {
     Colocated,
 }
 
+/// The attributes of the .glif off-curve `<point>` a [`Handle`] was read from, which the handle
+/// itself can't hold
+#[cfg_attr(feature = "glifserde", derive(Serialize, Deserialize))]
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct HandleAttributes {
+    #[cfg_attr(feature = "glifserde", serde(default))]
+    pub name: Option<String>,
+    #[cfg_attr(feature = "glifserde", serde(default))]
+    pub identifier: Option<String>,
+    /// The off-curve point's lib in `public.objectLibs`, if it has one
+    #[cfg_attr(feature = "glifserde", serde(default))]
+    pub lib: Option<plist::Dictionary>,
+}
+
+impl HandleAttributes {
+    pub fn is_empty(&self) -> bool {
+        self.name.is_none() && self.identifier.is_none() && self.lib.is_none()
+    }
+}
+
+impl From<&GlifPoint> for HandleAttributes {
+    fn from(gp: &GlifPoint) -> Self {
+        Self {
+            name: gp.name.clone(),
+            identifier: gp.identifier.clone(),
+            ..Default::default()
+        }
+    }
+}
+
 impl From<Option<&GlifPoint>> for Handle {
     fn from(point: Option<&GlifPoint>) -> Handle {
         match point {
@@ -130,13 +173,59 @@ impl From<()> for Handle {
 ///     point.data = Some(MyPointData::Point(true));
 /// }
 /// ```
+///
+/// Data is only kept in memory, unless the type opts in to being written to .glif files, in each
+/// object's lib in `public.objectLibs`, by naming a key for it there:
+///
+/// ```rust
+/// # #[derive(Debug, Copy, Clone, Default, serde::Serialize, serde::Deserialize)]
+/// # pub struct MyPointData(bool);
+/// use glifparser::point::ObjectLib;
+///
+/// impl glifparser::PointData for MyPointData {
+///     const OBJECT_LIB: Option<ObjectLib<Self>> = Some(ObjectLib::new("com.example.myPointData"));
+/// }
+/// ```
+///
+/// As `public.objectLibs` is keyed by identifier, points, anchors and guidelines with data but no
+/// identifier are given one when written. The data of anchors and guidelines is only written if
+/// it's not the default.
 #[cfg(feature = "glifserde")]
-pub trait PointData where Self: Clone + Default + Debug + Serialize {}
+pub trait PointData where Self: Clone + Default + Debug + Serialize {
+    /// Where this data is kept in its object's lib, or `None` not to write it to .glif files.
+    const OBJECT_LIB: Option<ObjectLib<Self>> = None;
+}
 #[cfg(not(feature = "glifserde"))]
 pub trait PointData where Self: Clone + Default + Debug {}
 impl PointData for () {}
 impl<PD: PointData> IsValid for PD {}
 
+/// Where [`PointData`] is kept in its object's lib, and how it's read back from there, which
+/// needs it to be [`Deserialize`].
+#[cfg(feature = "glifserde")]
+#[derive(Debug)]
+pub struct ObjectLib<PD> {
+    /// The key of the data in its object's lib
+    pub key: &'static str,
+    read: fn(&plist::Value) -> Result<PD, plist::Error>,
+}
+
+#[cfg(feature = "glifserde")]
+impl<PD: DeserializeOwned> ObjectLib<PD> {
+    /// Keep the data under `key`, reading it back with its [`Deserialize`] impl.
+    pub const fn new(key: &'static str) -> Self {
+        Self { key, read: plist::from_value }
+    }
+}
+
+#[cfg(feature = "glifserde")]
+impl<PD> ObjectLib<PD> {
+    /// The data from its value in its object's lib.
+    pub fn read(&self, value: &plist::Value) -> Result<PD, plist::Error> {
+        (self.read)(value)
+    }
+}
+
 /// A Skia-friendly point
 #[cfg_attr(feature = "glifserde", derive(Serialize, Deserialize))]
 #[derive(Debug, Default, Clone, PartialEq)]
@@ -154,6 +243,31 @@ pub struct Point<PD: PointData> {
     pub smooth: bool,
     #[cfg_attr(feature = "glifserde", serde(default))]
     pub data: Option<PD>,
+    /// .glif `<point identifier>`
+    #[cfg_attr(feature = "glifserde", serde(default))]
+    pub identifier: Option<String>,
+    /// The point's lib in `public.objectLibs`, if it has one. [`Point::data`] is kept here when
+    /// written, if its type opts in to that. Unlike other objects' libs, this is an `Option`, so
+    /// points can be built in `const`s.
+    #[cfg_attr(feature = "glifserde", serde(default))]
+    pub lib: Option<plist::Dictionary>,
+    /// The attributes of the off-curve point [`Point::a`] was read from. A quadratic off-curve
+    /// point is both the `a` of the point before it and the `b` of the point after it; its
+    /// attributes are kept on the `b`.
+    #[cfg_attr(feature = "glifserde", serde(default))]
+    pub a_attributes: HandleAttributes,
+    /// The attributes of the off-curve point [`Point::b`] was read from
+    #[cfg_attr(feature = "glifserde", serde(default))]
+    pub b_attributes: HandleAttributes,
+    /// A `QCurve` point which the .glif didn't have, but which the GLIF spec implies midway between
+    /// two consecutive quadratic off-curve points. See [`Point::is_implied`].
+    #[cfg_attr(feature = "glifserde", serde(default))]
+    pub implied: bool,
+    /// The single off-curve point a `Curve` point was read after, which the GLIF spec makes a
+    /// quadratic curve. The handles are set to the same curve as a cubic; as long as they're
+    /// unchanged, this is written back instead of them.
+    #[cfg_attr(feature = "glifserde", serde(default))]
+    pub curve_control: Option<(f32, f32)>,
 }
 
 /// For use by ``Point::handle_or_colocated``
@@ -188,7 +302,20 @@ impl<PD: PointData> Point<PD> {
     /// Make a point from its x and y position, handles and type
     pub fn from_fields((x, y): (f32, f32), (a, b): (Handle, Handle), smooth: bool, ptype: PointType, name: Option<String>, data: Option<PD>) -> Point<PD> {
         #[cfg(debug_assertions)] Self::check_ptype(ptype);
-        Point { x, y, a, b, smooth, ptype, name, data }
+        Point { x, y, a, b, smooth, ptype, name, data, ..Default::default() }
+    }
+
+    /// Whether the point is [`implied`](Point::implied) and still midway between its handles, so
+    /// can be left out when writing .glif. An implied point that's been moved, or given an
+    /// identifier or lib, is written out.
+    pub fn is_implied(&self) -> bool {
+        if self.identifier.is_some() || self.lib.is_some() {
+            return false
+        }
+        match (self.implied, self.ptype, self.a, self.b) {
+            (true, PointType::QCurve, Handle::At(ax, ay), Handle::At(bx, by)) => (bx + ax) / 2. == self.x && (by + ay) / 2. == self.y,
+            _ => false,
+        }
     }
 
     pub fn handle(&self, which: WhichHandle) -> Handle {
@@ -202,6 +329,15 @@ impl<PD: PointData> Point<PD> {
         }
     }
 
+    /// The attributes of the off-curve point `which` handle was read from, if it's `A` or `B`
+    pub fn handle_attributes(&self, which: WhichHandle) -> Option<&HandleAttributes> {
+        match which {
+            WhichHandle::A => Some(&self.a_attributes),
+            WhichHandle::B => Some(&self.b_attributes),
+            WhichHandle::Neither => None,
+        }
+    }
+
     /// Return an x, y position for a point, or one of its handles. If called with
     /// WhichHandle::Neither, return position for point.
     pub fn handle_or_colocated(
@@ -319,13 +455,13 @@ impl From<Handle> for PointType {
 
 impl From<&str> for PointType {
     fn from(s: &str) -> Self {
-        PointType::from_str(s).unwrap()
+        PointType::from_str(s).unwrap_or_default()
     }
 }
 
 impl From<&str> for WhichHandle {
     fn from(s: &str) -> Self {
-        WhichHandle::from_str(s).unwrap()
+        WhichHandle::from_str(s).unwrap_or_default()
     }
 }
 
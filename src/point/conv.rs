@@ -13,7 +13,11 @@ impl<PD: PointData> From<&Point<PD>> for Handle {
 impl GlifPoint {
     pub fn from_handle<PD: PointData>(point: &Point<PD>, wh: WhichHandle) -> Self {
         let (x, y) = point.handle_or_colocated(wh, &|f| f, &|f| f);
-        GlifPoint::from_x_y_type((x, y), PointType::OffCurve)
+        let gp = GlifPoint::from_x_y_type((x, y), PointType::OffCurve);
+        match point.handle_attributes(wh) {
+            Some(attributes) => gp.attributes(attributes),
+            None => gp,
+        }
     }
 }
 
@@ -25,6 +29,7 @@ impl<PD: PointData> From<&Point<PD>> for GlifPoint {
             y: p.y.into(),
             ptype: p.ptype,
             name: p.name.clone(),
+            identifier: p.identifier.clone(),
             smooth: p.smooth,
             ..Self::default()
         }
@@ -38,6 +43,7 @@ impl<PD: PointData> From<&GlifPoint> for Point<PD> {
             y: gp.y.into(),
             ptype: gp.ptype,
             name: gp.name.clone(),
+            identifier: gp.identifier.clone(),
             smooth: gp.smooth,
             ..Self::default()
         }
@@ -17,6 +17,9 @@ impl GlifPoint {
             Some(name) => el.attributes.insert("name".to_owned(), name.to_string()),
             None => None,
         };
+        if let Some(identifier) = &self.identifier {
+            el.attributes.insert("identifier".to_owned(), identifier.clone());
+        }
         if self.smooth {
             debug_assert!(self.ptype != PointType::OffCurve);
             el.attributes.insert("smooth".to_owned(), "yes".to_owned());
@@ -55,10 +55,8 @@ pub trait ToGlifString {
 
 impl ToGlifString for String {
     fn to_glif_string(mut self) -> GlifString {
-        while let Err(GlifStringConversionError::HasControlCharacter{ idx, .. }) = GlifString::try_from(&self) {
-            self.remove(idx);
-        }
-        self.try_into().unwrap()
+        self.retain(|c| !c.is_ascii_control());
+        GlifString(self)
     }
 }
 
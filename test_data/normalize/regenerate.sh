@@ -0,0 +1,46 @@
+#!/bin/sh
+# Regenerate a.normalized.glif, the expected output of tests/normalize.rs, by running ufonormalizer
+# (`pip install ufonormalizer`) on a.glif. ufonormalizer works on whole UFOs, so a.glif is put in a
+# minimal one.
+set -e
+cd "$(dirname "$0")"
+ufo="$(mktemp -d)/normalize.ufo"
+mkdir -p "$ufo/glyphs"
+cat > "$ufo/metainfo.plist" <<EOF
+<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE plist PUBLIC "-//Apple//DTD PLIST 1.0//EN" "http://www.apple.com/DTDs/PropertyList-1.0.dtd">
+<plist version="1.0">
+<dict>
+	<key>creator</key>
+	<string>org.unifiedfontobject.normalizer</string>
+	<key>formatVersion</key>
+	<integer>3</integer>
+</dict>
+</plist>
+EOF
+cat > "$ufo/layercontents.plist" <<EOF
+<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE plist PUBLIC "-//Apple//DTD PLIST 1.0//EN" "http://www.apple.com/DTDs/PropertyList-1.0.dtd">
+<plist version="1.0">
+<array>
+	<array>
+		<string>public.default</string>
+		<string>glyphs</string>
+	</array>
+</array>
+</plist>
+EOF
+cat > "$ufo/glyphs/contents.plist" <<EOF
+<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE plist PUBLIC "-//Apple//DTD PLIST 1.0//EN" "http://www.apple.com/DTDs/PropertyList-1.0.dtd">
+<plist version="1.0">
+<dict>
+	<key>a</key>
+	<string>a.glif</string>
+</dict>
+</plist>
+EOF
+cp a.glif "$ufo/glyphs/a.glif"
+ufonormalizer --all --no-mod-times --float-precision 7 "$ufo"
+cp "$ufo/glyphs/a.glif" a.normalized.glif
+rm -r "$(dirname "$ufo")"
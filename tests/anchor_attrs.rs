@@ -0,0 +1,19 @@
+static GLIF: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<glyph name="a" format="2">
+  <advance width="500" />
+  <anchor x="250" y="0" name="bottom" color="1,0,0,0.5" identifier="anchor0" />
+  <anchor x="250" y="500" name="top" />
+</glyph>"#;
+
+#[test]
+fn test_anchor_color_identifier() {
+    let glif: glifparser::Glif<()> = glifparser::glif::read(GLIF).unwrap();
+    assert_eq!(glif.anchors[0].identifier, Some(String::from("anchor0")));
+    assert!(glif.anchors[0].color.is_some());
+    assert_eq!((glif.anchors[1].color, &glif.anchors[1].identifier), (None, &None));
+
+    let newxml = glifparser::glif::write(&glif).unwrap();
+    assert!(newxml.contains(r#"identifier="anchor0""#) && newxml.contains("color="));
+    let newglif: glifparser::Glif<()> = glifparser::glif::read(&newxml).unwrap();
+    assert_eq!(newglif.anchors, glif.anchors);
+}
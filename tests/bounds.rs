@@ -0,0 +1,58 @@
+use glifparser::outline::Bounds as _;
+use glifparser::{Glif, GlifComponent, IntegerOrFloat};
+use kurbo::Rect;
+use std::collections::HashMap;
+
+const BUMP: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<glyph name="bump" format="2">
+  <outline>
+    <contour>
+      <point x="100" y="0" type="line"/>
+      <point x="100" y="100"/>
+      <point x="0" y="100"/>
+      <point x="0" y="0" type="curve"/>
+    </contour>
+  </outline>
+</glyph>
+"#;
+
+#[test]
+fn test_outline_bounds() {
+    let glif: Glif<()> = glifparser::glif::read(BUMP).unwrap();
+    let outline = glif.outline.as_ref().unwrap();
+    // The curve from (100, 0) to (0, 0) peaks at three quarters of its handles' height
+    assert_eq!(outline.bounds(), Some(Rect::new(0., 0., 100., 75.)));
+    assert_eq!(outline.control_bounds(), Some(Rect::new(0., 0., 100., 100.)));
+    assert_eq!(outline[0].bounds(), outline.bounds());
+    assert_eq!(glifparser::Outline::<()>::new().bounds(), None);
+
+    let quad = r#"<glyph name="arch" format="2"><outline><contour><point x="0" y="0" type="qcurve"/><point x="50" y="100"/><point x="100" y="0" type="qcurve"/></contour></outline></glyph>"#;
+    let glif: Glif<()> = glifparser::glif::read(quad).unwrap();
+    assert_eq!(glif.outline.unwrap().bounds(), Some(Rect::new(0., 0., 100., 50.)));
+}
+
+#[test]
+fn test_glif_bounds() {
+    let bump: Glif<()> = glifparser::glif::read(BUMP).unwrap();
+    let mut provider = HashMap::new();
+    provider.insert(String::from("bump"), bump.clone());
+
+    let mut glif: Glif<()> = Glif::new();
+    glif.name = String::from("bumps");
+    assert_eq!(glif.bounds_with(&provider).unwrap(), None);
+    let mut component = GlifComponent::new();
+    component.base = String::from("bump");
+    component.xOffset = IntegerOrFloat::Float(200.);
+    component.yOffset = IntegerOrFloat::Float(-50.);
+    glif.components.vec.push(component);
+    glif.outline = bump.outline.clone();
+    assert_eq!(glif.bounds_with(&provider).unwrap(), Some(Rect::new(0., -50., 300., 75.)));
+    assert_eq!(glif.control_bounds_with(&provider).unwrap(), Some(Rect::new(0., -50., 300., 100.)));
+
+    #[cfg(feature = "mfek")]
+    {
+        let mfek: glifparser::MFEKGlif<()> = bump.into();
+        assert_eq!(mfek.layers[0].bounds(), Some(Rect::new(0., 0., 100., 75.)));
+        assert_eq!(mfek.layers[0].control_bounds(), Some(Rect::new(0., 0., 100., 100.)));
+    }
+}
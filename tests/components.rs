@@ -41,3 +41,228 @@ fn test_components() {
     assert!(flatxml.len() > 0);
     //fs::write("/tmp/out.glif", flatxml);
 }
+
+fn component_glif(name: &str, bases: &[(&str, f32)]) -> glifparser::Glif<()> {
+    let mut glif = glifparser::Glif::new();
+    glif.name = name.to_string();
+    glif.components.root = name.to_string();
+    for (base, x_offset) in bases {
+        let mut component = glifparser::GlifComponent::new();
+        component.base = base.to_string();
+        component.xOffset = glifparser::IntegerOrFloat::Float(*x_offset);
+        glif.components.vec.push(component);
+    }
+    glif
+}
+
+#[test]
+fn test_component_providers() {
+    use glifparser::error::GlifParserError;
+    use glifparser::{Point, PointType};
+    use std::collections::HashMap;
+
+    let mut bar = component_glif("bar", &[]);
+    bar.outline = Some(vec![[(0., 0.), (10., 0.), (10., 100.), (0., 100.)].iter().map(|&xy| Point::from_x_y_type(xy, PointType::Line)).collect()]);
+    let mut provider: HashMap<String, glifparser::Glif<()>> = HashMap::new();
+    provider.insert(String::from("bar"), bar);
+    provider.insert(String::from("twobars"), component_glif("twobars", &[("bar", 0.), ("bar", 50.)]));
+    provider.insert(String::from("loop1"), component_glif("loop1", &[("loop2", 0.)]));
+    provider.insert(String::from("loop2"), component_glif("loop2", &[("loop1", 0.)]));
+
+    let flattened = component_glif("H", &[("twobars", 0.), ("bar", 200.)]).flattened_with(&mut None, &provider).unwrap();
+    let mut lefts: Vec<f32> = flattened.outline.unwrap().iter().map(|c| c[0].x).collect();
+    lefts.sort_by(f32::total_cmp);
+    assert_eq!(lefts, [0., 50., 200.]);
+
+    let missing = component_glif("x", &[("nothing", 0.)]).flattened_with(&mut None, &provider);
+    assert!(matches!(missing, Err(GlifParserError::GlifComponentBaseMissing(base)) if base == "nothing"));
+    let cyclical = component_glif("x", &[("loop1", 0.)]).flattened_with(&mut None, &provider);
+    assert!(matches!(cyclical, Err(GlifParserError::GlifComponentsCyclical(..))));
+
+    // A glyph set resolves components as the filesystem does, but sees unsaved edits
+    let mut glyphs: glifparser::GlyphSet<()> = glifparser::GlyphSet::open("test_data/TT2020Base.ufo/glyphs").unwrap();
+    let gershayim = glyphs.read("gershayim").unwrap();
+    assert_eq!(gershayim.flattened_with(&mut None, &glyphs).unwrap().outline, gershayim.flattened(&mut None).unwrap().outline);
+    glyphs.get_mut("acute").unwrap().components.vec.clear();
+    assert_eq!(gershayim.flattened_with(&mut None, &glyphs).unwrap().outline, Some(vec![]));
+}
+
+#[test]
+fn test_flattening_cache() {
+    use glifparser::component::{ComponentProvider, FlatteningCache};
+    use glifparser::error::GlifParserError;
+    use glifparser::{Contour, Glif, GlifComponent, Point, PointType};
+    use std::collections::HashMap;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// Counts the base glyphs asked for.
+    struct Counting(HashMap<String, Glif<()>>, AtomicUsize);
+    impl ComponentProvider<()> for Counting {
+        fn base_glif(&self, component: &GlifComponent) -> Result<Glif<()>, GlifParserError> {
+            self.1.fetch_add(1, Ordering::Relaxed);
+            self.0.base_glif(component)
+        }
+    }
+
+    let square = |x: f32| -> Contour<()> { [(x, 0.), (x + 10., 0.), (x + 10., 10.), (x, 10.)].iter().map(|&xy| Point::from_x_y_type(xy, PointType::Line)).collect() };
+    let mut bar = component_glif("bar", &[]);
+    bar.outline = Some(vec![square(0.)]);
+    let mut glyphs = HashMap::new();
+    glyphs.insert(String::from("bar"), bar);
+    glyphs.insert(String::from("twobars"), component_glif("twobars", &[("bar", 0.), ("bar", 50.)]));
+    glyphs.insert(String::from("loop1"), component_glif("loop1", &[("loop2", 0.)]));
+    glyphs.insert(String::from("loop2"), component_glif("loop2", &[("loop1", 0.)]));
+    let cache = FlatteningCache::new(Counting(glyphs, AtomicUsize::new(0)));
+
+    let mut h = component_glif("H", &[("twobars", 100.), ("bar", 300.)]);
+    h.outline = Some(vec![square(-100.)]);
+    let font = vec![h, component_glif("I", &[("twobars", 0.)]), component_glif("x", &[("loop1", 0.)]), component_glif("y", &[("loop2", 0.)])];
+    let flattened = cache.flattened_all(&font);
+    let h = flattened[0].as_ref().unwrap();
+    assert!(h.components.vec.is_empty());
+    let lefts: Vec<f32> = h.outline.as_ref().unwrap().iter().map(|c| c[0].x).collect();
+    assert_eq!(lefts, [-100., 100., 150., 300.]);
+    let provider = &cache.provider().0;
+    assert_eq!(font[0].flattened_with(&mut None, provider).unwrap().outline, h.outline);
+    assert_eq!(flattened[1].as_ref().unwrap().outline.as_ref().unwrap().len(), 2);
+    assert!(matches!(flattened[2], Err(GlifParserError::GlifComponentsCyclical(..))));
+    assert!(matches!(flattened[3], Err(GlifParserError::GlifComponentsCyclical(..))));
+    // twobars, bar, loop1 and loop2, once each
+    assert_eq!(cache.provider().1.load(Ordering::Relaxed), 4);
+
+    #[cfg(feature = "parallel")]
+    {
+        let parallel = cache.flattened_all_parallel(&font);
+        assert_eq!(parallel[0].as_ref().unwrap().outline, h.outline);
+        assert!(parallel[2].is_err());
+        assert_eq!(cache.provider().1.load(Ordering::Relaxed), 4);
+    }
+}
+
+#[test]
+fn test_flattened_mirrored_with_anchors() {
+    use glifparser::anchor::{Anchor, AnchorType};
+    use glifparser::component::{FlattenOptions, FlatteningCache};
+    use glifparser::{IntegerOrFloat::Float, Point, PointType};
+    use std::collections::HashMap;
+
+    let area = |contour: &Vec<Point<()>>| -> f32 { (0..contour.len()).map(|i| { let (p, q) = (&contour[i], &contour[(i + 1) % contour.len()]); p.x * q.y - q.x * p.y }).sum::<f32>() / 2. };
+    let anchor = |name: &str, x: f32, y: f32| Anchor::new(x, y, Some(name.to_string()), (), AnchorType::Base).identifier(name);
+
+    let mut bar = component_glif("bar", &[]);
+    bar.outline = Some(vec![[(0., 0.), (10., 0.), (10., 100.), (0., 100.)].iter().map(|&xy| Point::from_x_y_type(xy, PointType::Line)).collect()]);
+    bar.anchors = vec![anchor("top", 5., 100.), anchor("bottom", 5., 0.)];
+    let mut provider: HashMap<String, glifparser::Glif<()>> = HashMap::new();
+    provider.insert(String::from("bar"), bar);
+    // bar, and bar mirrored across x = 50
+    let mut glif = component_glif("pair", &[("bar", 0.), ("bar", 100.)]);
+    glif.components.vec[1].xScale = Float(-1.);
+    glif.anchors = vec![anchor("top", 50., 120.)];
+
+    let options = FlattenOptions { anchors: true };
+    let flattened = [
+        glif.flattened_with_options(&mut None, &provider, options).unwrap(),
+        FlatteningCache::with_options(&provider, options).flattened(&glif).unwrap(),
+    ];
+    for flattened in flattened {
+        let outline = flattened.outline.unwrap();
+        assert_eq!(outline.len(), 2);
+        assert!(outline.iter().all(|c| area(c) > 0.));
+        let anchors: Vec<_> = flattened.anchors.iter().map(|a| (a.class.as_deref().unwrap(), a.x, a.y, a.identifier.as_deref())).collect();
+        assert_eq!(anchors, [("top", 50., 120., Some("top")), ("bottom", 5., 0., None)]);
+    }
+    assert!(glif.flattened_with(&mut None, &provider).unwrap().anchors.len() == 1);
+}
+
+#[test]
+fn test_flattened_mirrored_curves_and_open_contours() {
+    use glifparser::component::FlatteningCache;
+    use glifparser::outline::Winding as _;
+    use glifparser::{Handle::{At, Colocated}, IntegerOrFloat::Float, PointType};
+    use std::collections::HashMap;
+
+    let hook: glifparser::Glif<()> = glifparser::glif::read(r#"<glyph name="hook" format="2">
+  <outline>
+    <contour>
+      <point x="0" y="0" type="line"/>
+      <point x="100" y="0" type="line"/>
+      <point x="100" y="50"/>
+      <point x="50" y="100"/>
+      <point x="0" y="100" type="curve"/>
+    </contour>
+    <contour>
+      <point x="0" y="0" type="move"/>
+      <point x="0" y="100" type="line"/>
+      <point x="50" y="150"/>
+      <point x="100" y="150"/>
+      <point x="150" y="100" type="curve"/>
+      <point x="150" y="0" type="line"/>
+    </contour>
+  </outline>
+</glyph>"#).unwrap();
+    let mut provider: HashMap<String, glifparser::Glif<()>> = HashMap::new();
+    provider.insert(String::from("hook"), hook);
+    let mut glif = component_glif("mirrored", &[("hook", 300.)]);
+    glif.components.vec[0].xScale = Float(-1.);
+
+    let flattened = [
+        glif.flattened_with(&mut None, &provider).unwrap(),
+        FlatteningCache::new(&provider).flattened(&glif).unwrap(),
+    ];
+    for flattened in flattened {
+        // Written and read back, so no handle may be on a point whose type can't keep it
+        let reread: glifparser::Glif<()> = glifparser::glif::read(&glifparser::glif::write(&flattened).unwrap()).unwrap();
+        let outline = reread.outline.unwrap();
+        assert_eq!(outline, flattened.outline.unwrap());
+        assert!(outline[0].is_counter_clockwise());
+        let closed: Vec<_> = outline[0].iter().map(|p| (p.x, p.y, p.ptype, p.a, p.b)).collect();
+        assert_eq!(closed, [
+            (300., 0., PointType::Line, Colocated, Colocated),
+            (300., 100., PointType::Line, At(250., 100.), Colocated),
+            (200., 0., PointType::Curve, Colocated, At(200., 50.)),
+        ]);
+        let open: Vec<_> = outline[1].iter().map(|p| (p.x, p.y, p.ptype, p.a, p.b)).collect();
+        assert_eq!(open, [
+            (150., 0., PointType::Move, Colocated, Colocated),
+            (150., 100., PointType::Line, At(200., 150.), Colocated),
+            (300., 100., PointType::Curve, Colocated, At(250., 150.)),
+            (300., 0., PointType::Line, Colocated, Colocated),
+        ]);
+    }
+}
+
+#[test]
+fn test_flattened_one_base_twice() {
+    use glifparser::component::FlatteningCache;
+    use glifparser::pedantry::{Level, Mend, Pedantry};
+    use std::collections::HashMap;
+
+    let dot: glifparser::Glif<()> = glifparser::glif::read(r#"<glyph name="dot" format="2">
+  <outline>
+    <contour identifier="dot">
+      <point x="0" y="0" type="line" identifier="p0"/>
+      <point x="50" y="0" identifier="h0"/>
+      <point x="50" y="50" type="curve" identifier="p1"/>
+    </contour>
+  </outline>
+</glyph>"#).unwrap();
+    let mut provider: HashMap<String, glifparser::Glif<()>> = HashMap::new();
+    provider.insert(String::from("dot"), dot.clone());
+    // Two dots, beside a contour of its own sharing the dot's identifier
+    let mut glif = component_glif("colon", &[("dot", 0.), ("dot", 100.)]);
+    glif.outline = dot.outline.clone();
+
+    let flattened = [
+        glif.flattened_with(&mut None, &provider).unwrap(),
+        FlatteningCache::new(&provider).flattened(&glif).unwrap(),
+    ];
+    for flattened in flattened {
+        let outline = flattened.outline.as_ref().unwrap();
+        let identifiers: Vec<_> = outline.iter().map(|c| c.identifier.as_deref()).collect();
+        assert_eq!(identifiers, [Some("dot"), None, None]);
+        let xml = glifparser::glif::write(&flattened).unwrap();
+        let strict = Pedantry::new(Level::Ufo, Mend::Never);
+        let reread: glifparser::Glif<()> = glifparser::glif::read_pedantic(&xml, strict).unwrap();
+        assert_eq!(reread.outline.unwrap().len(), 3);
+    }
+}
@@ -0,0 +1,59 @@
+use glifparser::error::Severity;
+use glifparser::pedantry::{Level, Mend};
+use glifparser::Pedantry;
+
+static GLIF: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<glyph name="bad" format="2">
+  <advance width="500.5" />
+  <unicode hex="zz" />
+  <unicode hex="0062" />
+  <anchor y="10" name="top" />
+  <outline>
+    <contour>
+      <point x="0" y="0" type="line" identifier="p" />
+      <point x="0" y="nope" type="line" />
+      <point x="100" y="100" type="line" identifier="p" />
+      <point x="100" y="0" smooth="yes" />
+      <point x="100" y="0" type="curve" />
+    </contour>
+    <component />
+  </outline>
+</glyph>"#;
+
+#[test]
+fn test_diagnostics() {
+    assert!(glifparser::glif::read::<()>(GLIF).is_err());
+
+    let (glif, diagnostics) = glifparser::glif::read_with_diagnostics::<()>(GLIF, Pedantry::default());
+    let glif = glif.unwrap();
+    assert_eq!(glif.width, Some(500));
+    assert_eq!(glif.unicode, vec!['b']);
+    assert!(glif.anchors.is_empty());
+    assert_eq!(glif.outline.as_ref().unwrap()[0].len(), 3);
+    assert!(glif.components.vec.is_empty());
+
+    let severities: Vec<Severity> = diagnostics.iter().map(|d| d.severity).collect();
+    let (e, m, w) = (Severity::Error, Severity::Mend, Severity::Warning);
+    assert_eq!(severities, vec![m, e, e, e, w, m, e]);
+    let unicode_error = &diagnostics[1];
+    assert_eq!(unicode_error.location.path, vec![(String::from("unicode"), 0)]);
+    assert_eq!(unicode_error.location.line, Some(4));
+    assert_eq!(diagnostics[3].location.path.last(), Some(&(String::from("point"), 1)));
+    // Duplicate identifiers are located at the element repeating them, and kept at this level
+    let duplicate = &diagnostics[4];
+    assert_eq!(duplicate.message, r#"Duplicate identifier "p""#);
+    assert_eq!(duplicate.location.path.last(), Some(&(String::from("point"), 2)));
+    assert_eq!(duplicate.location.line, Some(11));
+    assert_eq!(glif.outline.as_ref().unwrap()[0][1].identifier.as_deref(), Some("p"));
+
+    let (glif, diagnostics) = glifparser::glif::read_with_diagnostics::<()>(GLIF, Pedantry::new(Level::Ufo, Mend::Always));
+    assert_eq!(glif.unwrap().outline.as_ref().unwrap()[0][1].identifier, None);
+    let removed = diagnostics.iter().find(|d| d.message == r#"Removed duplicate identifier "p""#).unwrap();
+    assert_eq!((removed.severity, removed.location.line), (m, Some(11)));
+
+    let (_, diagnostics) = glifparser::glif::read_with_diagnostics::<()>(GLIF, Pedantry::new(Level::Ufo, Mend::Never));
+    assert_eq!(diagnostics.iter().filter(|d| d.severity.is_error()).count(), 6);
+
+    let (glif, diagnostics) = glifparser::glif::read_with_diagnostics::<()>("<glyph", Pedantry::default());
+    assert!(glif.is_none() && diagnostics.len() == 1);
+}
@@ -0,0 +1,79 @@
+use glifparser::outline::{CorrectDirection as _, Direction, DirectionConvention, Winding as _};
+use glifparser::{Contour, Glif, Point, PointData, PointType};
+
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "glifserde", derive(serde::Serialize))]
+struct Tag(u8);
+impl PointData for Tag {}
+
+fn square(x: f32, y: f32, side: f32, clockwise: bool) -> Contour<Tag> {
+    let mut ret: Contour<Tag> = [(x, y), (x + side, y), (x + side, y + side), (x, y + side)].iter().map(|&xy| Point::from_x_y_type(xy, PointType::Line)).collect();
+    if clockwise {
+        ret.swap(1, 3);
+    }
+    ret
+}
+
+#[test]
+fn test_signed_area() {
+    assert_eq!(square(0., 0., 100., false).signed_area(), 10000.);
+    assert_eq!(square(0., 0., 100., false).direction(), Some(Direction::CounterClockwise));
+    assert!(square(0., 0., 100., true).is_clockwise());
+    let mut open = square(0., 0., 100., false);
+    open[0].ptype = PointType::Move;
+    assert_eq!(open.signed_area(), 10000.);
+    assert_eq!(Contour::<Tag>::new().direction(), None);
+
+    // A quadratic arch over (0, 0)–(100, 0), of area two thirds of its control triangle's base by its height
+    let arch = r#"<glyph name="arch" format="2"><outline><contour><point x="100" y="0" type="qcurve"/><point x="50" y="100"/><point x="0" y="0" type="qcurve"/></contour></outline></glyph>"#;
+    let glif: Glif<()> = glifparser::glif::read(arch).unwrap();
+    let area = glif.outline.as_ref().unwrap()[0].signed_area();
+    assert!((area - 100. * 50. * 2. / 3.).abs() < 1e-6, "{}", area);
+
+    // Reversed, a line and a quadratic curve each stay what they were
+    let arch = arch.replace(r#"<point x="100" y="0" type="qcurve"/>"#, r#"<point x="100" y="0" type="line"/>"#);
+    let mut glif: Glif<()> = glifparser::glif::read(&arch).unwrap();
+    glif.outline.as_mut().unwrap().correct_direction(DirectionConvention::TrueType);
+    let read: Glif<()> = glifparser::glif::read(&glifparser::glif::write(&glif).unwrap()).unwrap();
+    assert!((read.outline.unwrap()[0].signed_area() + area).abs() < 1e-6);
+}
+
+#[test]
+fn test_correct_direction() {
+    // A clockwise square with a clockwise counter, beside a counter-clockwise square
+    let mut outline = vec![square(0., 0., 100., true), square(25., 25., 50., true), square(200., 0., 100., false)];
+    outline[0][1].name = Some(String::from("corner"));
+    outline[0][1].smooth = true;
+    outline[0][1].data = Some(Tag(1));
+    let corner = outline[0][1].clone();
+
+    outline.correct_direction(DirectionConvention::PostScript);
+    let directions: Vec<_> = outline.iter().map(|c| c.direction().unwrap()).collect();
+    assert_eq!(directions, [Direction::CounterClockwise, Direction::Clockwise, Direction::CounterClockwise]);
+    let moved = outline[0].iter().find(|p| p.name == corner.name).unwrap();
+    assert_eq!((moved.x, moved.y, moved.smooth, &moved.data), (corner.x, corner.y, true, &corner.data));
+
+    outline.correct_direction(DirectionConvention::TrueType);
+    let directions: Vec<_> = outline.iter().map(|c| c.direction().unwrap()).collect();
+    assert_eq!(directions, [Direction::Clockwise, Direction::CounterClockwise, Direction::Clockwise]);
+}
+
+#[test]
+fn test_correct_direction_round_trip() {
+    let mut glif: Glif<()> = glifparser::glif::read_from_filename("test_data/TT2020Base.ufo/glyphs/grave.glif").unwrap();
+    let outline = glif.outline.as_mut().unwrap();
+    let areas: Vec<f64> = outline.iter().map(|c| c.signed_area()).collect();
+    outline.correct_direction(DirectionConvention::TrueType);
+    let corrected: Vec<f64> = outline.iter().map(|c| c.signed_area()).collect();
+    let outer = corrected.iter().copied().max_by(|a, b| a.abs().total_cmp(&b.abs())).unwrap();
+    assert!(outer < 0.);
+
+    // Reversed curves keep their shape once written
+    let read: Glif<()> = glifparser::glif::read(&glifparser::glif::write(&glif).unwrap()).unwrap();
+    let read_areas: Vec<f64> = read.outline.unwrap().iter().map(|c| c.signed_area()).collect();
+    assert_eq!(read_areas.len(), areas.len());
+    for ((area, corrected), read_area) in areas.iter().zip(corrected).zip(read_areas) {
+        assert!((area.abs() - corrected.abs()).abs() < 1e-3);
+        assert!((corrected - read_area).abs() < 1e-3);
+    }
+}
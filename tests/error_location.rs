@@ -0,0 +1,33 @@
+static GLIF: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<glyph name="L" format="2">
+  <advance width="500" />
+  <outline>
+    <contour>
+      <point x="0" y="0" type="line" />
+      <point x="0" y="700" type="line" />
+    </contour>
+    <contour>
+      <point x="100" y="0" type="line" />
+      <point x="100" y="seven hundred" type="line" />
+    </contour>
+  </outline>
+</glyph>"#;
+
+#[test]
+fn test_error_location() {
+    let err = glifparser::glif::read::<()>(GLIF).unwrap_err();
+    let location = err.location().unwrap();
+    assert_eq!((location.line, location.column), (Some(11), Some(7)));
+    assert_eq!(location.path, vec![(String::from("outline"), 0), (String::from("contour"), 1), (String::from("point"), 1)]);
+    assert!(matches!(err.without_location(), glifparser::error::GlifParserError::GlifInputError(_)));
+
+    let dir = tempfile::tempdir().unwrap();
+    let filename = dir.path().join("L_.glif");
+    std::fs::write(&filename, GLIF).unwrap();
+    let err = glifparser::glif::read_from_filename::<_, ()>(&filename).unwrap_err();
+    assert_eq!(err.location().unwrap().filename.as_ref(), Some(&filename));
+    assert!(err.to_string().contains("L_.glif:11:7"));
+
+    let err = glifparser::glif::read::<()>("<glyph name=\"L\" format=\"2\">\n<advance width=\"500\">\n</glyph>").unwrap_err();
+    assert_eq!(err.location().unwrap().line, Some(3));
+}
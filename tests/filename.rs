@@ -0,0 +1,55 @@
+use glifparser::glif::{name_to_filename, user_name_to_file_name};
+use glifparser::Glif;
+
+use std::collections::HashSet;
+
+#[test]
+fn test_spec_examples() {
+    // From the UFO 3 spec's conventions page
+    for (name, file_name) in [
+        ("a", "a"), ("A", "A_"), ("AE", "A_E_"), ("Ae", "A_e"), ("ae", "ae"), ("aE", "aE_"),
+        ("a.alt", "a.alt"), ("A.alt", "A_.alt"), ("A.Alt", "A_.A_lt"), ("A.aLt", "A_.aL_t"),
+        ("A.alT", "A_.alT_"), ("T_H", "T__H_"), ("T_h", "T__h"), ("t_h", "t_h"),
+        ("F_F_I", "F__F__I_"), ("f_f_i", "f_f_i"), ("Aacute_V.swash", "A_acute_V_.swash"),
+        (".notdef", "_notdef"), ("con", "_con"), ("CON", "C_O_N_"), ("con.alt", "_con.alt"),
+        ("alt.con", "alt._con"), ("Z", "Z_"), ("a:b*c", "a_b_c"), ("Ω", "Ω_"),
+    ] {
+        assert_eq!(name_to_filename(name, false), file_name, "{}", name);
+    }
+    // Only the spec's reserved names, as in fontTools
+    for (name, file_name) in [("com4", "_com4"), ("com5", "com5"), ("lpt3", "_lpt3"), ("lpt4", "lpt4")] {
+        assert_eq!(name_to_filename(name, false), file_name, "{}", name);
+    }
+    assert_eq!(name_to_filename("Z", true), "Z_.glif");
+    assert_eq!(user_name_to_file_name(".hidden", "glyphs.", "", &HashSet::new()), "glyphs..hidden");
+
+    let long = name_to_filename(&"€".repeat(200), true);
+    assert_eq!(long.len(), 254);
+    assert!(long.ends_with("€.glif"));
+}
+
+#[test]
+fn test_clashes() {
+    let mut existing: HashSet<String> = HashSet::new();
+    existing.insert(String::from("a.glif"));
+    let first = user_name_to_file_name("a", "", ".glif", &existing);
+    assert_eq!(first, "a000000000000001.glif");
+    existing.insert(first.to_lowercase());
+    assert_eq!(user_name_to_file_name("a", "", ".glif", &existing), "a000000000000002.glif");
+
+    let long = "a".repeat(300);
+    existing.insert(name_to_filename(&long, true));
+    let clashed = user_name_to_file_name(&long, "", ".glif", &existing);
+    assert_eq!(clashed.len(), 255);
+    assert!(clashed.ends_with("000000000000001.glif"));
+
+    let mut glif: Glif<()> = Glif::new();
+    glif.name = String::from("a");
+    for (filename, sane) in [("a.glif", true), ("a000000000000002.glif", true), ("A_.glif", false), ("a2.glif", false)] {
+        glif.filename = Some(format!("glyphs/{}", filename).into());
+        assert_eq!(glif.filename_is_sane().unwrap(), sane, "{}", filename);
+    }
+    glif.name = long;
+    glif.filename = Some(clashed.into());
+    assert!(glif.filename_is_sane().unwrap());
+}
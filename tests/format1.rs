@@ -0,0 +1,42 @@
+use glifparser::pedantry::{Level, Mend};
+use glifparser::Pedantry;
+
+static GLIF1: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<glyph name="A" format="1">
+  <advance width="600" />
+  <unicode hex="0041" />
+  <outline>
+    <contour>
+      <point x="0" y="0" type="line" />
+      <point x="300" y="700" type="line" />
+      <point x="600" y="0" type="line" />
+    </contour>
+    <contour>
+      <point x="300" y="700" type="move" name="top" />
+    </contour>
+  </outline>
+</glyph>"#;
+
+#[test]
+fn test_format1_upgrade() {
+    let glif: glifparser::Glif<()> = glifparser::glif::read(GLIF1).unwrap();
+    assert_eq!(glif.outline.as_ref().unwrap().len(), 1);
+    assert_eq!(glif.anchors.len(), 1);
+    assert_eq!(glif.anchors[0].class, Some(String::from("top")));
+    assert_eq!((glif.anchors[0].x, glif.anchors[0].y), (300., 700.));
+    let newxml = glifparser::glif::write(&glif).unwrap();
+    assert!(newxml.contains(r#"format="2""#) && newxml.contains("<anchor"));
+
+    let rejected: Result<glifparser::Glif<()>, _> = glifparser::glif::read_pedantic(GLIF1, Pedantry::new(Level::GlifParser, Mend::Never));
+    assert!(rejected.is_err());
+}
+
+#[test]
+fn test_format1_write() {
+    let glif: glifparser::Glif<()> = glifparser::glif::read(GLIF1).unwrap();
+    let oldxml = glifparser::glif::write_format_1(&glif).unwrap();
+    assert!(oldxml.contains(r#"format="1""#) && !oldxml.contains("<anchor"));
+    let newglif: glifparser::Glif<()> = glifparser::glif::read(&oldxml).unwrap();
+    assert_eq!(newglif.anchors, glif.anchors);
+    assert_eq!(newglif.outline, glif.outline);
+}
@@ -0,0 +1,57 @@
+use glifparser::pedantry::{Level, Mend};
+use glifparser::Pedantry;
+
+fn glif_with_minor(minor: &str) -> String {
+    format!(r#"<?xml version="1.0" encoding="UTF-8"?>
+<glyph name="space" format="2" formatMinor="{}">
+  <advance width="250" />
+  <unicode hex="0020" />
+</glyph>"#, minor)
+}
+
+#[test]
+fn test_format_minor() {
+    let glif: glifparser::Glif<()> = glifparser::glif::read(&glif_with_minor("0")).unwrap();
+    assert_eq!(glif.format_minor, 0);
+    let newxml = glifparser::glif::write(&glif).unwrap();
+    assert!(!newxml.contains("formatMinor"));
+
+    let glif: glifparser::Glif<()> = glifparser::glif::read(&glif_with_minor("7")).unwrap();
+    assert_eq!(glif.format_minor, 7);
+    assert_eq!(glif.required_format_minor(), 0);
+    let newglif: glifparser::Glif<()> = glifparser::glif::read(&glifparser::glif::write(&glif).unwrap()).unwrap();
+    assert_eq!(newglif.format_minor, 0);
+
+    let strict: Result<glifparser::Glif<()>, _> = glifparser::glif::read_pedantic(&glif_with_minor("7"), Pedantry::new(Level::Ufo, Mend::Always));
+    assert!(strict.is_err());
+    let invalid: Result<glifparser::Glif<()>, _> = glifparser::glif::read(&glif_with_minor("-1"));
+    assert!(invalid.is_err());
+}
+
+#[test]
+fn test_format_minor_written() {
+    // No format 2.x feature is known yet, so no glyph needs a formatMinor, whatever it was read as
+    let mut glif: glifparser::Glif<()> = glifparser::glif::read(&glif_with_minor("0")).unwrap();
+    glif.format_minor = 3;
+    let newxml = glifparser::glif::write(&glif).unwrap();
+    assert!(newxml.contains(r#"<glyph name="space" format="2">"#));
+
+    // A newer minor is read as 2.0 with a warning at the GlifParser level, whatever the mend policy
+    let (glif, diagnostics) = glifparser::glif::read_with_diagnostics::<()>(&glif_with_minor("3"), Pedantry::default());
+    assert_eq!(glif.unwrap().format_minor, 3);
+    assert!(diagnostics.iter().any(|d| d.severity.is_warning() && d.message.starts_with("Reading GLIF format 2.3 as if it were 2.0")));
+    let tolerated: Result<glifparser::Glif<()>, _> = glifparser::glif::read_pedantic(&glif_with_minor("3"), Pedantry::new(Level::GlifParser, Mend::Never));
+    assert!(tolerated.is_ok());
+
+    // Written losslessly, it's kept as long as the <glyph> tag is, but not once it's rewritten
+    let mut lossless = glifparser::glif::read_lossless::<()>(&glif_with_minor("3")).unwrap();
+    assert_eq!(glifparser::glif::write_lossless(&lossless).unwrap(), glif_with_minor("3"));
+    lossless.glif.name = String::from("space.alt");
+    let written = glifparser::glif::write_lossless(&lossless).unwrap();
+    assert!(written.contains(r#"<glyph name="space.alt" format="2">"#));
+    let reread: glifparser::Glif<()> = glifparser::glif::read(&written).unwrap();
+    assert_eq!(reread.format_minor, 0);
+
+    let format_1 = glif_with_minor("1").replace(r#"format="2""#, r#"format="1""#);
+    assert!(glifparser::glif::read::<()>(&format_1).is_err());
+}
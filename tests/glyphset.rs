@@ -0,0 +1,137 @@
+use glifparser::error::GlifParserError;
+use glifparser::GlyphSet;
+
+use std::fs;
+use std::path::Path;
+
+const GLYPHS: &str = "test_data/TT2020Base.ufo/glyphs";
+
+#[test]
+fn test_read_glyphset() {
+    let mut glyphs: GlyphSet<()> = GlyphSet::open(GLYPHS).unwrap();
+    assert_eq!(glyphs.names().collect::<Vec<_>>(), ["NULL", "NULL2", "acute", "gershayim", "grave"]);
+    assert_eq!(glyphs.layer_info.color.map(|c| c.to_string()).as_deref(), Some("1,0.75,0,0.7"));
+    assert!(glyphs.layer_info.lib.contains_key("com.example.shown"));
+    assert!(glyphs.loaded().next().is_none());
+
+    let gershayim = glyphs.get("gershayim").unwrap();
+    assert_eq!(gershayim.filename.as_deref(), Some(Path::new(GLYPHS).join("gershayim.glif").as_path()));
+    assert_eq!(gershayim.components.vec[0].filename.as_deref(), Some(Path::new(GLYPHS).join("acute.glif").as_path()));
+    let grave = glyphs.read("grave").unwrap();
+    assert_eq!(grave.components.vec[0].filename.as_deref(), Some(Path::new(GLYPHS).join("N_U_L_L_2.glif").as_path()));
+    assert!(glyphs.is_loaded("gershayim") && !glyphs.is_loaded("grave"));
+
+    assert!(matches!(glyphs.get("a"), Err(GlifParserError::GlyphNotInGlyphSet(name)) if name == "a"));
+    glyphs.load_all().unwrap();
+    assert_eq!(glyphs.loaded().count(), 5);
+
+    #[cfg(feature = "parallel")]
+    {
+        let mut parallel: GlyphSet<()> = GlyphSet::open(GLYPHS).unwrap();
+        parallel.load_all_parallel().unwrap();
+        for (name, glif) in glyphs.loaded() {
+            assert_eq!(parallel.get(name).unwrap(), glif);
+        }
+    }
+}
+
+#[test]
+fn test_bad_glyphset() {
+    let dir = tempfile::tempdir().unwrap();
+    let err = GlyphSet::<()>::open(dir.path()).unwrap_err();
+    assert_eq!(err.location().and_then(|l| l.filename.clone()), Some(dir.path().join("contents.plist")));
+
+    fs::write(dir.path().join("contents.plist"), r#"<?xml version="1.0" encoding="UTF-8"?>
+<plist version="1.0">
+<dict>
+  <key>a</key>
+  <string>../a.glif</string>
+</dict>
+</plist>"#).unwrap();
+    let err = GlyphSet::<()>::open(dir.path()).unwrap_err();
+    assert!(err.to_string().contains("../a.glif"), "{}", err);
+}
+
+#[cfg(unix)]
+#[test]
+fn test_write_glyphset() {
+    use std::os::unix::fs::MetadataExt as _;
+
+    let dir = tempfile::tempdir().unwrap();
+    for entry in fs::read_dir(GLYPHS).unwrap() {
+        let path = entry.unwrap().path();
+        fs::copy(&path, dir.path().join(path.file_name().unwrap())).unwrap();
+    }
+    fs::write(dir.path().join("stray.glif"), "").unwrap();
+    let inode = |file: &str| fs::metadata(dir.path().join(file)).unwrap().ino();
+
+    let read = |file: &str| fs::read(dir.path().join(file)).unwrap();
+    let original_gershayim = read("gershayim.glif");
+
+    let mut glyphs: GlyphSet<()> = GlyphSet::open(dir.path()).unwrap();
+    let null = inode("N_U_L_L_.glif");
+    glyphs.load_all().unwrap();
+    glyphs.save().unwrap();
+    // Loaded but not edited, so left as the tool which wrote it did
+    assert_eq!(inode("N_U_L_L_.glif"), null, "unchanged glyph rewritten");
+    assert_eq!(read("gershayim.glif"), original_gershayim);
+
+    glyphs.get_mut("gershayim").unwrap().unicode.clear();
+    assert!(glyphs.is_modified("gershayim") && !glyphs.is_modified("grave"));
+    glyphs.save().unwrap();
+    assert_ne!(read("gershayim.glif"), original_gershayim);
+    let gershayim = inode("gershayim.glif");
+    glyphs.save().unwrap();
+    assert_eq!(inode("gershayim.glif"), gershayim, "unchanged glyph rewritten");
+
+    let mut a = glyphs.read("acute").unwrap();
+    a.name = String::from("A");
+    glyphs.insert(a);
+    glyphs.remove("grave");
+    glyphs.rename("acute", "Acute").unwrap();
+    glyphs.layer_info = Default::default();
+    glyphs.save().unwrap();
+    assert_eq!(inode("N_U_L_L_.glif"), null, "unchanged glyph rewritten");
+
+    let mut files: Vec<_> = fs::read_dir(dir.path()).unwrap().map(|e| e.unwrap().file_name().into_string().unwrap()).collect();
+    files.sort();
+    // The stray file was never in contents.plist, so isn't the glyph set's to delete
+    assert_eq!(files, ["A_.glif", "A_cute.glif", "N_U_L_L_.glif", "N_U_L_L_2.glif", "contents.plist", "gershayim.glif", "stray.glif"]);
+
+    let mut reopened: GlyphSet<()> = GlyphSet::open(dir.path()).unwrap();
+    assert_eq!(reopened.contents(), glyphs.contents());
+    assert_eq!(reopened.get("Acute").unwrap().name, "Acute");
+    assert_eq!(reopened.get("A").unwrap().filename.as_deref(), Some(dir.path().join("A_.glif").as_path()));
+}
+
+#[test]
+fn test_new_glyphset_over_existing() {
+    let dir = tempfile::tempdir().unwrap();
+    for entry in fs::read_dir(GLYPHS).unwrap() {
+        let path = entry.unwrap().path();
+        fs::copy(&path, dir.path().join(path.file_name().unwrap())).unwrap();
+    }
+    let before = fs::read_dir(dir.path()).unwrap().count();
+
+    let mut glyphs: GlyphSet<()> = GlyphSet::new(dir.path());
+    let mut glif = glifparser::Glif::new();
+    glif.name = String::from("a");
+    glyphs.insert(glif);
+    let e = glyphs.save().unwrap_err();
+    assert!(matches!(e.without_location(), GlifParserError::GlifFileIoError(_)));
+    assert_eq!(e.location().unwrap().filename.as_deref(), Some(dir.path()));
+    assert_eq!(fs::read_dir(dir.path()).unwrap().count(), before);
+    assert!(dir.path().join("gershayim.glif").exists());
+
+    // Into a new directory, it saves, and then deletes the glyphs it wrote once they're removed
+    let path = dir.path().join("new");
+    let mut glyphs: GlyphSet<()> = GlyphSet::new(&path);
+    let mut glif = glifparser::Glif::new();
+    glif.name = String::from("a");
+    glyphs.insert(glif);
+    glyphs.save().unwrap();
+    assert!(path.join("a.glif").exists());
+    glyphs.remove("a");
+    glyphs.save().unwrap();
+    assert!(!path.join("a.glif").exists());
+}
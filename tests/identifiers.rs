@@ -0,0 +1,113 @@
+use glifparser::outline::Reverse as _;
+use glifparser::pedantry::{Level, Mend};
+use glifparser::Pedantry;
+
+static GLIF: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<glyph name="I" format="2">
+  <advance width="300" />
+  <outline>
+    <contour identifier="contour0">
+      <point x="100" y="0" type="line" identifier="point0" />
+      <point x="100" y="700" type="line" />
+      <point x="200" y="700" type="line" identifier="point2" />
+      <point x="200" y="0" type="line" />
+    </contour>
+    <contour identifier="contour1">
+      <point x="300" y="0" type="qcurve" identifier="on0" />
+      <point x="350" y="100" identifier="off0" />
+      <point x="400" y="100" identifier="off1" />
+      <point x="450" y="0" type="qcurve" />
+      <point x="450" y="-100" identifier="off2" />
+      <point x="300" y="-100" identifier="off3" />
+      <point x="300" y="-50" type="curve" />
+    </contour>
+    <contour>
+      <point x="0" y="0" type="line" identifier="point0" />
+      <point x="50" y="50" type="line" />
+      <point x="0" y="50" type="line" />
+    </contour>
+  </outline>
+</glyph>"#;
+
+#[test]
+fn test_identifiers_roundtrip() {
+    let glif: glifparser::Glif<()> = glifparser::glif::read_pedantic(GLIF, Pedantry::new(Level::Ufo, Mend::Always)).unwrap();
+    let outline = glif.outline.as_ref().unwrap();
+    let identifiers: Vec<_> = outline.iter().map(|c| c.identifier.as_deref()).collect();
+    assert_eq!(identifiers, [Some("contour0"), Some("contour1"), None]);
+    assert_eq!(outline[0][0].identifier, Some(String::from("point0")));
+    assert_eq!(outline[0][2].identifier, Some(String::from("point2")));
+    // Off-curve points' identifiers are kept with their handles
+    assert_eq!(outline[1][1].b_attributes.identifier.as_deref(), Some("off0"));
+    assert_eq!(outline[1][2].b_attributes.identifier.as_deref(), Some("off1"));
+    assert_eq!(outline[1][2].a_attributes.identifier.as_deref(), Some("off2"));
+    assert_eq!(outline[1][3].b_attributes.identifier.as_deref(), Some("off3"));
+    // The duplicate is mended away at the UFO level
+    assert_eq!(outline[2][0].identifier, None);
+
+    let newxml = glifparser::glif::write(&glif).unwrap();
+    assert!(newxml.contains(r#"<contour identifier="contour0">"#));
+    assert_eq!(newxml.matches(r#"identifier="point0""#).count(), 1);
+    for identifier in ["off0", "off1", "off2", "off3"] {
+        assert!(newxml.contains(&format!(r#"identifier="{}""#, identifier)), "{}", identifier);
+    }
+    let newglif: glifparser::Glif<()> = glifparser::glif::read(&newxml).unwrap();
+    assert_eq!(newglif.outline, glif.outline);
+}
+
+#[test]
+fn test_identifiers_follow_contours() {
+    let glif: glifparser::Glif<()> = glifparser::glif::read(GLIF).unwrap();
+    let mut outline = glif.outline.unwrap();
+    outline.swap(0, 1);
+    outline.remove(2);
+    outline[0].reverse();
+    assert_eq!(outline[0].identifier.as_deref(), Some("contour1"));
+    let reversed = glifparser::Glif { outline: Some(outline), ..glifparser::Glif::new() };
+    let newxml = glifparser::glif::write(&reversed).unwrap();
+    for identifier in ["contour0", "contour1", "on0", "off0", "off1", "off2", "off3"] {
+        assert_eq!(newxml.matches(&format!(r#"identifier="{}""#, identifier)).count(), 1, "{}", identifier);
+    }
+    assert!(newxml.find(r#"identifier="contour1""#) < newxml.find(r#"identifier="contour0""#));
+    // The quadratic off-curve points are written in reverse order
+    assert!(newxml.find(r#"identifier="off1""#) < newxml.find(r#"identifier="off0""#));
+}
+
+#[cfg(feature = "mfek")]
+#[test]
+fn test_identifiers_mfek() {
+    use glifparser::MFEKGlif;
+
+    let glif: glifparser::Glif<()> = glifparser::glif::read(GLIF).unwrap();
+    let mfek = MFEKGlif::from(glif.clone());
+    assert_eq!(mfek.layers[0].outline[1].identifier.as_deref(), Some("contour1"));
+    let back = glifparser::Glif::try_from(mfek).unwrap();
+    assert_eq!(back.outline, glif.outline);
+}
+
+#[test]
+fn test_identifiers_duplicate() {
+    let tolerated: glifparser::Glif<()> = glifparser::glif::read_pedantic(GLIF, Pedantry::new(Level::GlifParser, Mend::Never)).unwrap();
+    assert_eq!(tolerated.outline.unwrap()[2][0].identifier, Some(String::from("point0")));
+
+    let rejected: Result<glifparser::Glif<()>, _> = glifparser::glif::read_pedantic(GLIF, Pedantry::new(Level::Ufo, Mend::Never));
+    assert!(rejected.is_err());
+}
+
+#[test]
+fn test_identifiers_syntax() {
+    let long = "x".repeat(101);
+    for bad in [long.as_str(), "tab\there", "é"] {
+        let glif = GLIF.replace(r#"identifier="point2""#, &format!(r#"identifier="{}""#, bad));
+        let tolerated: glifparser::Glif<()> = glifparser::glif::read(&glif).unwrap();
+        assert_eq!(tolerated.outline.unwrap()[0][2].identifier.as_deref(), Some(bad));
+        let mended: glifparser::Glif<()> = glifparser::glif::read_pedantic(&glif, Pedantry::new(Level::Ufo, Mend::Always)).unwrap();
+        assert_eq!(mended.outline.unwrap()[0][2].identifier, None);
+        let rejected: Result<glifparser::Glif<()>, _> = glifparser::glif::read_pedantic(&glif, Pedantry::new(Level::Ufo, Mend::Never));
+        assert!(rejected.is_err(), "{:?}", bad);
+    }
+    let longest = "x".repeat(100);
+    let glif = GLIF.replace(r#"identifier="point2""#, &format!(r#"identifier="{}""#, longest));
+    let read: glifparser::Glif<()> = glifparser::glif::read(&glif).unwrap();
+    assert_eq!(read.outline.unwrap()[0][2].identifier.as_deref(), Some(longest.as_str()));
+}
@@ -0,0 +1,26 @@
+use std::path::Path;
+
+#[test]
+fn test_read_write_io() {
+    let file = std::fs::File::open("test_data/TT2020Base.ufo/glyphs/acute.glif").unwrap();
+    let glif: glifparser::Glif<()> = glifparser::glif::read_from_reader(file, None).unwrap();
+    assert_eq!(glif.filename, None);
+
+    let mut out: Vec<u8> = vec![];
+    glifparser::glif::write_to_writer(&glif, &mut out, None).unwrap();
+    assert_eq!(String::from_utf8(out.clone()).unwrap(), glifparser::glif::write(&glif).unwrap());
+
+    let filename = Path::new("glyphs/acute.glif");
+    let reread: glifparser::Glif<()> = glifparser::glif::read_from_reader(&out[..], Some(filename)).unwrap();
+    assert_eq!(reread.filename.as_deref(), Some(filename));
+    assert_eq!(reread.outline, glif.outline);
+}
+
+#[test]
+fn test_read_io_error_has_filename() {
+    let bad: &[u8] = b"<glyph name=\"a\" format=\"2\"><advance width=\"wide\"/></glyph>";
+    let err = glifparser::glif::read_from_reader::<_, ()>(bad, Some(Path::new("a.glif"))).unwrap_err();
+    assert_eq!(err.location().unwrap().filename.as_deref(), Some(Path::new("a.glif")));
+    let not_utf8: &[u8] = b"<glyph name=\"\xff\" format=\"2\"/>";
+    assert!(glifparser::glif::read_from_reader::<_, ()>(not_utf8, None).is_err());
+}
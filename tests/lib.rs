@@ -0,0 +1,73 @@
+#![cfg(feature = "glifserde")]
+use glifparser::error::GlifParserError;
+use glifparser::pedantry::{Level, Mend, Pedantry};
+use glifparser::{Color, Glif};
+
+use serde::{Deserialize, Serialize};
+use std::str::FromStr as _;
+
+const GLIF: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<glyph name="a" format="2">
+  <lib>
+    <dict>
+      <key>public.markColor</key>
+      <string>1,0,0,1</string>
+      <key>public.objectLibs</key>
+      <dict>
+        <key>p1</key>
+        <dict>
+          <key>com.example.smooth</key>
+          <true/>
+        </dict>
+      </dict>
+    </dict>
+  </lib>
+</glyph>
+"#;
+
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+struct Spacing {
+    left: String,
+    right: Option<String>,
+}
+
+#[test]
+fn test_lib_accessors() {
+    let mut glif: Glif<()> = glifparser::glif::read(GLIF).unwrap();
+    assert_eq!(glif.lib.mark_color(), Some(Color::from_str("1,0,0,1").unwrap()));
+    assert_eq!(glif.lib.vertical_origin(), None);
+    assert_eq!(glif.lib.object_lib("p1").and_then(|lib| lib.get("com.example.smooth")), Some(&plist::Value::Boolean(true)));
+
+    let spacing = Spacing { left: String::from("o"), right: None };
+    glif.lib.set("com.example.spacing", &spacing).unwrap();
+    glif.lib.set_mark_color(None);
+    glif.lib.set_object_lib("p1", None);
+    glif.lib.set_object_lib("c1", Some(plist::Dictionary::from_iter([("com.example.locked", true)])));
+
+    let glif: Glif<()> = glifparser::glif::read(&glifparser::glif::write(&glif).unwrap()).unwrap();
+    assert_eq!(glif.lib.get::<Spacing>("com.example.spacing").unwrap(), Some(spacing));
+    assert!(matches!(glif.lib.get::<u32>("com.example.spacing"), Err(GlifParserError::GlifLibError)));
+    assert_eq!(glif.lib.get::<u32>("com.example.nothing").unwrap(), None);
+    assert_eq!(glif.lib.mark_color(), None);
+    assert_eq!(glif.lib.object_libs().map(|libs| libs.keys().map(String::as_str).collect::<Vec<_>>()), Some(vec!["c1"]));
+    assert!(glif.lib.validate().is_ok());
+}
+
+#[test]
+fn test_lib_validation() {
+    let bad = GLIF.replace("1,0,0,1", "red");
+
+    // Glifparser's own level lets anything through; the accessor ignores what it can't read
+    let glif: Glif<()> = glifparser::glif::read(&bad).unwrap();
+    assert_eq!(glif.lib.mark_color(), None);
+    assert!(glif.lib.value("public.markColor").is_some());
+    assert!(glif.lib.validate().is_err());
+
+    let strict = Pedantry::new(Level::Ufo, Mend::Never);
+    assert!(glifparser::glif::read_pedantic::<()>(&bad, strict).is_err());
+
+    let (glif, mends) = glifparser::glif::read_with_mends::<()>(&bad, Pedantry::new(Level::Ufo, Mend::Always)).unwrap();
+    assert!(glif.lib.value("public.markColor").is_none());
+    assert!(glif.lib.object_lib("p1").is_some());
+    assert_eq!(mends.len(), 1);
+}
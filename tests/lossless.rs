@@ -0,0 +1,103 @@
+use glifparser::glif::LosslessGlif;
+
+static GLIF: &str = r#"<?xml version='1.0' encoding='UTF-8'?>
+<!-- Drawn by hand -->
+<glyph format="2" name="a" xmlns:my="urn:example">
+  <advance width="500"/>
+  <unicode hex="0061"/>
+  <my:unknown keep="me"/>
+  <anchor x="250" y="600" name="top"/>
+  <outline>
+    <contour>
+      <point x="0"   y="0" type="line"/>   <!-- origin -->
+      <point x="500" y="0" type="line"/>
+      <point x="250" y="500" type="line"/>
+    </contour>
+  </outline>
+</glyph>
+"#;
+
+#[test]
+fn test_lossless_untouched() {
+    let glif: LosslessGlif<()> = glifparser::glif::read_lossless(GLIF).unwrap();
+    assert!(!glif.is_modified());
+    assert_eq!(glifparser::glif::write_lossless(&glif).unwrap(), GLIF);
+}
+
+#[test]
+fn test_lossless_patches_changes() {
+    let mut glif: LosslessGlif<()> = glifparser::glif::read_lossless(GLIF).unwrap();
+    glif.width = Some(600);
+    glif.anchors.clear();
+    glif.note = Some(String::from("hi"));
+    let written = glifparser::glif::write_lossless(&glif).unwrap();
+    assert_eq!(written, GLIF
+        .replace(r#"<advance width="500"/>"#, r#"<advance width="600"/>"#)
+        .replace("\n  <anchor x=\"250\" y=\"600\" name=\"top\"/>", "")
+        .replace("  </outline>\n", "  </outline>\n  <note>hi</note>\n"));
+
+    let reread: glifparser::Glif<()> = glifparser::glif::read(&written).unwrap();
+    assert_eq!(reread, glif.glif);
+}
+
+static TWO_CONTOURS: &str = r#"<?xml version='1.0' encoding='UTF-8'?>
+<glyph format="2" name="b">
+  <anchor x="0" y="0" name="bottom"/>  <!-- kept -->
+  <anchor x="0" y="700" name="top"/>
+  <outline>
+    <contour>
+      <point x="0"   y="0" type="line"/>   <!-- origin -->
+      <point x="100" y="0" type="line"/>
+      <point x="100" y="700" type="line"/>
+    </contour>
+    <contour>
+      <point x="200" y="0" type="line"/>
+      <point x="300" y="0" type="line"/>
+      <point x="300" y="100" type="line"/>
+    </contour>
+    <component base="a"/>  <!-- also kept -->
+  </outline>
+</glyph>
+"#;
+
+#[test]
+fn test_lossless_patches_elements() {
+    use glifparser::{GlifComponent, Point, PointType};
+
+    let mut glif: LosslessGlif<()> = glifparser::glif::read_lossless(TWO_CONTOURS).unwrap();
+    glif.outline.as_mut().unwrap()[1][2].y = 200.;
+    glif.anchors[1].y = 800.;
+    let written = glifparser::glif::write_lossless(&glif).unwrap();
+    assert_eq!(written, TWO_CONTOURS
+        .replace(r#"<anchor x="0" y="700" name="top"/>"#, r#"<anchor x="0" y="800" name="top"/>"#)
+        .replace(r#"<point x="300" y="100" type="line"/>"#, r#"<point x="300" y="200" type="line"/>"#));
+
+    // Added contours go after the last, added components after the last component
+    let mut glif: LosslessGlif<()> = glifparser::glif::read_lossless(TWO_CONTOURS).unwrap();
+    let triangle = [(400., 0.), (500., 0.), (500., 100.)].iter().map(|&xy| Point::from_x_y_type(xy, PointType::Line)).collect();
+    glif.outline.as_mut().unwrap().push(triangle);
+    glif.components.vec.push(GlifComponent { base: String::from("c"), ..GlifComponent::new() });
+    let written = glifparser::glif::write_lossless(&glif).unwrap();
+    assert_eq!(written, TWO_CONTOURS
+        .replace("\n    <component base=\"a\"/>", concat!(
+            "\n    <contour>\n",
+            "      <point x=\"400\" y=\"0\" type=\"line\"/>\n",
+            "      <point x=\"500\" y=\"0\" type=\"line\"/>\n",
+            "      <point x=\"500\" y=\"100\" type=\"line\"/>\n",
+            "    </contour>\n",
+            "    <component base=\"a\"/>"))
+        .replace("<!-- also kept -->", concat!("<!-- also kept -->\n",
+            r#"    <component base="c" xScale="1" xyScale="0" yxScale="0" yScale="1" xOffset="0" yOffset="0"/>"#)));
+    let reread: glifparser::Glif<()> = glifparser::glif::read(&written).unwrap();
+    assert_eq!(reread.outline, glif.outline);
+    assert_eq!(reread.components.vec.iter().map(|c| &c.base).collect::<Vec<_>>(), ["a", "c"]);
+
+    // Removed ones take their lines with them
+    let mut glif: LosslessGlif<()> = glifparser::glif::read_lossless(TWO_CONTOURS).unwrap();
+    glif.outline.as_mut().unwrap().pop();
+    glif.anchors.pop();
+    let written = glifparser::glif::write_lossless(&glif).unwrap();
+    let second = TWO_CONTOURS.find("\n    <contour>\n      <point x=\"200\"").unwrap();
+    let second = &TWO_CONTOURS[second..TWO_CONTOURS.find("\n    <component").unwrap()];
+    assert_eq!(written, TWO_CONTOURS.replace(second, "").replace("\n  <anchor x=\"0\" y=\"700\" name=\"top\"/>", ""));
+}
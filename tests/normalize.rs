@@ -0,0 +1,22 @@
+#![cfg(feature = "glifserde")]
+use glifparser::glif::WriteOptions;
+
+// A glyph, and its normalization as ufonormalizer writes it with a float precision of 7, which
+// test_data/normalize/regenerate.sh regenerates. The glyph's contours come before its components,
+// as glifparser keeps them apart and so can't keep them interleaved.
+static GLIF: &str = include_str!("../test_data/normalize/a.glif");
+static NORMALIZED: &str = include_str!("../test_data/normalize/a.normalized.glif");
+
+#[test]
+fn test_normalized_write() {
+    let glif: glifparser::Glif<()> = glifparser::glif::read(GLIF).unwrap();
+    let options = WriteOptions::normalized().float_precision(Some(7));
+    let written = glifparser::glif::write_with_options(&glif, &options).unwrap();
+    assert_eq!(written, NORMALIZED);
+
+    let reread: glifparser::Glif<()> = glifparser::glif::read(&written).unwrap();
+    assert_eq!(glifparser::glif::write_with_options(&reread, &options).unwrap(), written);
+
+    let unsorted = glifparser::glif::write_with_options(&glif, &options.sort_lib_keys(false)).unwrap();
+    assert!(unsorted.find("public.verticalOrigin") < unsorted.find("com.example.b"));
+}
@@ -0,0 +1,122 @@
+#![cfg(feature = "glifserde")]
+use glifparser::anchor::{Anchor, AnchorType};
+use glifparser::component::GlifComponent;
+use glifparser::guideline::Guideline;
+use glifparser::point::ObjectLib;
+use glifparser::{Contour, Glif, Point, PointData, PointType};
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+struct Weight {
+    weight: f64,
+    tags: Vec<String>,
+}
+
+impl PointData for Weight {
+    const OBJECT_LIB: Option<ObjectLib<Self>> = Some(ObjectLib::new("com.example.weight"));
+}
+
+/// Point data which is only kept in memory
+#[derive(Clone, Debug, Default, Serialize)]
+struct InMemory;
+
+impl PointData for InMemory {}
+
+fn weight(weight: f64) -> Weight {
+    Weight { weight, tags: vec![String::from("tag")] }
+}
+
+#[test]
+fn test_point_data_round_trip() {
+    let mut glif: Glif<Weight> = Glif::new();
+    glif.name = String::from("a");
+    let mut contour: Contour<Weight> = [(0., 0.), (100., 0.), (50., 100.)].iter().map(|&xy| Point::from_x_y_type(xy, PointType::Line)).collect();
+    contour[0].identifier = Some(String::from("p0"));
+    contour[0].data = Some(weight(0.5));
+    // Given an identifier when written, to key its data by
+    contour[1].data = Some(weight(2.));
+    contour[0].lib = Some(plist::Dictionary::from_iter([("com.example.other", 1)]));
+    contour.lib.insert(String::from("com.example.contour"), true.into());
+    glif.outline = Some(vec![contour]);
+    glif.anchors.push(Anchor { data: weight(1.5), ..Anchor::new(50., 100., Some(String::from("top")), Weight::default(), AnchorType::Base).identifier("a0") });
+    glif.anchors.push(Anchor::new(50., 0., Some(String::from("bottom")), Weight::default(), AnchorType::Base).identifier("a1"));
+    glif.guidelines.push(Guideline { identifier: Some(String::from("g0")), data: weight(3.), ..Guideline::default() });
+    // That of an object which isn't there stays in the glyph's lib
+    glif.lib.set_object_lib("gone", Some(plist::Dictionary::from_iter([("com.example.other", 2)])));
+
+    let xml = glifparser::glif::write(&glif).unwrap();
+    assert!(xml.contains("public.objectLibs") && xml.contains("com.example.weight"));
+    let read: Glif<Weight> = glifparser::glif::read(&xml).unwrap();
+    let outline = read.outline.as_ref().unwrap();
+    assert_eq!(outline[0][0].data, Some(weight(0.5)));
+    assert_eq!((outline[0].identifier.as_deref(), outline[0].lib.get("com.example.contour")), (Some("id0"), Some(&true.into())));
+    assert_eq!((outline[0][1].identifier.as_deref(), &outline[0][1].data), (Some("id1"), &Some(weight(2.))));
+    assert_eq!(read.anchors[0].data, weight(1.5));
+    assert_eq!(read.anchors[1].data, Weight::default());
+    assert_eq!(read.guidelines[0].data, weight(3.));
+    // The object libs are moved onto their objects, less the data
+    assert_eq!(read.lib.object_libs().map(|libs| libs.keys().map(String::as_str).collect::<Vec<_>>()), Some(vec!["gone"]));
+    assert_eq!(outline[0][0].lib.as_ref().unwrap().keys().collect::<Vec<_>>(), ["com.example.other"]);
+    assert_eq!(glifparser::glif::write(&read).unwrap(), xml);
+
+    // Point data which doesn't opt in leaves the data in the object libs
+    let unit: Glif<()> = glifparser::glif::read(&xml).unwrap();
+    assert!(unit.anchors[0].lib.contains_key("com.example.weight"));
+    // Nor need it be Deserialize
+    let in_memory: Glif<InMemory> = glifparser::glif::read(&xml).unwrap();
+    assert!(in_memory.outline.unwrap()[0][0].data.is_none());
+}
+
+#[test]
+fn test_generated_identifiers() {
+    let mut glif: Glif<Weight> = Glif::new();
+    glif.name = String::from("a");
+    glif.anchors.push(Anchor { data: weight(1.), ..Anchor::new(0., 0., None, Weight::default(), AnchorType::Base) });
+    let mut component = GlifComponent::new();
+    component.base = String::from("b");
+    component.lib.insert(String::from("com.example.component"), true.into());
+    glif.components.vec.push(component);
+    glif.guidelines.push(Guideline { data: weight(2.), ..Guideline::default() });
+
+    let xml = glifparser::glif::write(&glif).unwrap();
+    for identifier in ["id0", "id1", "id2"] {
+        assert_eq!(xml.matches(&format!(r#"identifier="{}""#, identifier)).count(), 1, "{}", identifier);
+    }
+    let read: Glif<Weight> = glifparser::glif::read(&xml).unwrap();
+    assert_eq!((read.anchors[0].identifier.as_deref(), &read.anchors[0].data), (Some("id0"), &weight(1.)));
+    assert_eq!(read.components.vec[0].lib.get("com.example.component"), Some(&true.into()));
+    assert_eq!((read.guidelines[0].identifier.as_deref(), &read.guidelines[0].data), (Some("id2"), &weight(2.)));
+    assert!(read.lib.object_libs().is_none());
+}
+
+#[test]
+fn test_point_data_lossless() {
+    let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<glyph name="a" format="2">
+  <outline>
+    <contour>
+      <point x="0" y="0" type="line" identifier="p0"/>
+      <point x="10" y="0" type="line"/>
+    </contour>
+  </outline>
+</glyph>
+"#;
+    let mut lossless = glifparser::glif::read_lossless::<Weight>(xml).unwrap();
+    lossless.glif.outline.as_mut().unwrap()[0][0].data = Some(weight(1.));
+    lossless.glif.outline.as_mut().unwrap()[0][1].data = Some(weight(2.));
+    let written = glifparser::glif::write_lossless(&lossless).unwrap();
+    assert!(written.contains(r#"<point x="10" y="0" type="line" identifier="id0"/>"#));
+    let read: Glif<Weight> = glifparser::glif::read(&written).unwrap();
+    let outline = read.outline.unwrap();
+    assert_eq!((&outline[0][0].data, &outline[0][1].data), (&Some(weight(1.)), &Some(weight(2.))));
+}
+
+#[test]
+fn test_guideline_lib_default() {
+    let guideline: Guideline<()> = Guideline { identifier: Some(String::from("g0")), ..Guideline::default() };
+    let mut value = plist::to_value(&guideline).unwrap();
+    assert!(value.as_dictionary_mut().unwrap().remove("lib").is_some());
+    let read: Guideline<()> = plist::from_value(&value).unwrap();
+    assert_eq!(read, guideline);
+}
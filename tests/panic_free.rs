@@ -0,0 +1,44 @@
+use glifparser::error::GlifParserError;
+use glifparser::outline::{IntoPenOperations as _, Reverse as _};
+use glifparser::point::{Point, PointType};
+use glifparser::{glif, Contour, Glif, Pedantry};
+
+use std::fs;
+
+/// Every prefix of the fuzz corpus seeds must read, or fail to, without panicking.
+#[test]
+fn test_truncated_input() {
+    for entry in fs::read_dir("fuzz/corpus/read").unwrap() {
+        let xml = fs::read_to_string(entry.unwrap().path()).unwrap();
+        for (end, _) in xml.char_indices().step_by(3) {
+            let xml = &xml[..end];
+            let _ = glif::scan(xml);
+            let _ = glif::read_with_diagnostics::<()>(xml, Pedantry::default());
+            if let Ok(lossless) = glif::read_lossless::<()>(xml) {
+                let _ = glif::write_lossless(&lossless);
+            }
+            if let Ok(glif) = glif::read::<()>(xml) {
+                glif::write(&glif).unwrap();
+            }
+        }
+    }
+}
+
+#[test]
+fn test_bad_in_memory_outline() {
+    let line = |x: f32| Point::from_x_y_type((x, 0.), PointType::Line);
+    let mut qclose = line(100.);
+    qclose.ptype = PointType::QClose;
+    let glif: Glif<()> = Glif {
+        name: String::from("bad"),
+        outline: Some(vec![vec![line(0.), line(50.)].into(), vec![line(0.), qclose].into()]),
+        ..Glif::default()
+    };
+    assert!(matches!(glif::write(&glif), Err(GlifParserError::GlifOutlineHasBadPointType { ci: 1, pi: 1, ptype: PointType::QClose })));
+
+    let mut empty: Contour<()> = Contour::new();
+    empty.reverse();
+    assert!(matches!(empty.into_pen_operations(), Ok(ops) if ops.is_empty()));
+
+    assert!(Glif::<()>::try_from("<glyph").is_err());
+}
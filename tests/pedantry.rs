@@ -0,0 +1,63 @@
+use glifparser::pedantry::{Level, Mend};
+use glifparser::{Glif, Pedantry};
+
+static GLIF: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<glyph name="A" format="2">
+  <advance width="500.4" />
+  <anchor x="250.6" y="700" name="top" />
+  <guideline x="10.5" y="0" angle="90.5" />
+  <outline>
+    <contour>
+      <point x="0.4" y="0" type="line" />
+      <point x="250" y="700.5" type="line" />
+      <point x="500" y="0" type="line" />
+    </contour>
+    <component base="acute" xScale="0.5" xOffset="100.7" yOffset="20" />
+  </outline>
+</glyph>"#;
+
+#[test]
+fn test_truetype_rounds() {
+    let (glif, mends): (Glif<()>, _) = glifparser::glif::read_with_mends(GLIF, Pedantry::new(Level::TrueType, Mend::Always)).unwrap();
+    let outline = glif.outline.as_ref().unwrap();
+    assert_eq!((outline[0][0].x, outline[0][1].y), (0., 701.));
+    assert_eq!((glif.anchors[0].x, glif.guidelines[0].at.x), (251., 11.));
+    assert_eq!(f32::from(glif.guidelines[0].angle), 90.5);
+    let component = &glif.components.vec[0];
+    assert_eq!((f32::from(component.xOffset), f32::from(component.xScale)), (101., 0.5));
+    assert_eq!(glif.width, Some(500));
+
+    let mended: Vec<_> = mends.iter().map(|m| m.location.path.last().unwrap().0.as_str()).collect();
+    assert_eq!(mended, vec!["advance", "anchor", "guideline", "point", "point", "component"]);
+    assert_eq!(mends[0].message, "Floating point value given as <advance> width — OpenType `hmtx` / `vmtx` will truncate it, so we do too!");
+    assert_eq!(mends[1].message, "Rounded <anchor> x 250.6 to 251");
+    let glif: Glif<()> = glifparser::glif::read(&GLIF.replace("500.4", "500.6")).unwrap();
+    assert_eq!(glif.width, Some(500));
+
+    for mend in [Mend::Never, Mend::UfoSpecErrorsOnly, Mend::UfoSpecOutdatedOnly] {
+        assert!(glifparser::glif::read_pedantic::<()>(GLIF, Pedantry::new(Level::TrueType, mend)).is_err());
+    }
+}
+
+#[test]
+fn test_levels() {
+    // OpenType needs only anchors and advances to be integers
+    let (glif, mends): (Glif<()>, _) = glifparser::glif::read_with_mends(GLIF, Pedantry::new(Level::OpenType, Mend::Always)).unwrap();
+    assert_eq!((glif.outline.as_ref().unwrap()[0][0].x, glif.anchors[0].x), (0.4, 251.));
+    assert_eq!(mends.len(), 2);
+
+    // Floats are fine in UFO, so are kept whatever the mend policy, but advances are integers here
+    assert!(glifparser::glif::read_pedantic::<()>(GLIF, Pedantry::new(Level::Ufo, Mend::Never)).is_err());
+    let glif: Glif<()> = glifparser::glif::read_pedantic(&GLIF.replace("500.4", "500"), Pedantry::new(Level::Ufo, Mend::Never)).unwrap();
+    assert_eq!(glif.anchors[0].x, 250.6);
+    let glif: Glif<()> = glifparser::glif::read_pedantic(GLIF, Pedantry::new(Level::GlifParser, Mend::UfoSpecErrorsOnly)).unwrap();
+    assert_eq!(glif.width, Some(500));
+}
+
+#[test]
+fn test_bad_advances() {
+    for width in ["-500", "-0.5", "NaN", "inf", "-inf"] {
+        let glif = GLIF.replace("500.4", width);
+        assert!(glifparser::glif::read::<()>(&glif).is_err(), "{}", width);
+    }
+}
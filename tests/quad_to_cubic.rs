@@ -1,5 +1,5 @@
 use glifparser::outline::{GlifOutline, QuadToCubic as _};
-use glifparser::point::{GlifPoint, PointType};
+use glifparser::point::{GlifPoint, HandleAttributes, PointType};
 use glifparser::write;
 use glifparser::{Glif, Handle, Outline, Point};
 
@@ -16,6 +16,12 @@ static PARABOLA: [[Point<()>; 2]; 1] = {
             ptype: Move,
             smooth: false,
             data: None,
+            identifier: None,
+            lib: None,
+            a_attributes: HandleAttributes { name: None, identifier: None, lib: None },
+            b_attributes: HandleAttributes { name: None, identifier: None, lib: None },
+            implied: false,
+            curve_control: None,
         },
         Point {
             b: At(133.33333, 333.33334),
@@ -26,6 +32,12 @@ static PARABOLA: [[Point<()>; 2]; 1] = {
             ptype: Curve,
             smooth: false,
             data: None,
+            identifier: None,
+            lib: None,
+            a_attributes: HandleAttributes { name: None, identifier: None, lib: None },
+            b_attributes: HandleAttributes { name: None, identifier: None, lib: None },
+            implied: false,
+            curve_control: None,
         },
     ]]
 };
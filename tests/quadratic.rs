@@ -0,0 +1,115 @@
+use glifparser::point::PointType;
+use glifparser::Glif;
+
+// As written by glifparser, so that it round-trips byte for byte
+static GLIF: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<glyph name="o" format="2">
+  <outline>
+    <contour>
+      <point x="250" y="0" type="qcurve" smooth="yes"/>
+      <point x="400" y="0"/>
+      <point x="500" y="150"/>
+      <point x="500" y="350"/>
+      <point x="400" y="500"/>
+      <point x="250" y="500" type="qcurve" smooth="yes"/>
+      <point x="0" y="500"/>
+      <point x="0" y="0"/>
+    </contour>
+    <contour>
+      <point x="100" y="100"/>
+      <point x="200" y="100"/>
+      <point x="200" y="200"/>
+      <point x="100" y="200"/>
+    </contour>
+    <contour>
+      <point x="0" y="0" type="move"/>
+      <point x="50" y="100"/>
+      <point x="100" y="100" type="qcurve"/>
+      <point x="150" y="100"/>
+      <point x="200" y="50"/>
+      <point x="200" y="0" type="curve"/>
+      <point x="300" y="0" type="line"/>
+      <point x="350" y="50"/>
+      <point x="400" y="50"/>
+      <point x="450" y="0" type="qcurve"/>
+    </contour>
+    <contour>
+      <point x="600" y="0" name="start"/>
+      <point x="700" y="100" type="qcurve"/>
+      <point x="800" y="0" name="right"/>
+      <point x="700" y="-100" type="qcurve"/>
+    </contour>
+    <contour>
+      <point x="0" y="600" type="line"/>
+      <point x="150" y="750" name="single"/>
+      <point x="300" y="600" type="curve"/>
+    </contour>
+    <contour>
+      <point x="300" y="900"/>
+      <point x="0" y="900" type="curve"/>
+      <point x="150" y="750" type="line"/>
+    </contour>
+  </outline>
+</glyph>"#;
+
+#[test]
+fn test_quadratic_roundtrip() {
+    let glif: Glif<()> = glifparser::glif::read(GLIF).unwrap();
+    let outline = glif.outline.as_ref().unwrap();
+    assert!(outline.iter().flatten().all(|p| p.ptype != PointType::QClose && p.ptype != PointType::OffCurve));
+
+    // On-curve points are implied between each pair of consecutive off-curve points
+    let implied: Vec<_> = outline[0].iter().filter(|p| p.implied).map(|p| (p.x, p.y)).collect();
+    assert_eq!(implied, vec![(450., 75.), (500., 250.), (450., 425.), (0., 250.)]);
+    // Every on-curve point of an all-off-curve contour is implied
+    assert_eq!(outline[1].len(), 4);
+    assert!(outline[1].iter().all(|p| p.implied));
+    assert_eq!((outline[1][0].x, outline[1][0].y), (150., 100.));
+    assert_eq!(outline[2].iter().map(|p| p.ptype).collect::<Vec<_>>(),
+               vec![PointType::Move, PointType::QCurve, PointType::Curve, PointType::Line, PointType::QCurve, PointType::QCurve]);
+    // A closed contour is begun at its first on-curve point, but remembers the off-curve before it
+    assert_eq!((outline[3][0].x, outline[3].leading_off_curves), (700., 1));
+    assert_eq!(outline[3][0].b_attributes.name.as_deref(), Some("start"));
+    // A curve after a single off-curve point is quadratic, so is read as the same curve as a cubic
+    use glifparser::Handle::At;
+    assert_eq!((outline[4][0].a, outline[4][1].b, outline[4][1].curve_control), (At(100., 700.), At(200., 700.), Some((150., 750.))));
+    assert_eq!(outline[4][0].a_attributes.name.as_deref(), Some("single"));
+    assert_eq!((outline[5][0].x, outline[5][0].b, outline[5].leading_off_curves), (0., At(200., 900.), 1));
+
+    assert_eq!(glifparser::glif::write(&glif).unwrap().trim_end(), GLIF);
+
+    // A moved implied point is no longer implied, so is written out
+    let mut moved = glif.clone();
+    moved.outline.as_mut().unwrap()[1][0].y = 90.;
+    let newxml = glifparser::glif::write(&moved).unwrap();
+    assert!(newxml.contains(r#"<point x="150" y="90" type="qcurve" smooth="yes"/>"#));
+
+    // A quadratic curve whose handles have been moved is written as the cubic it now is
+    let mut moved = glif.clone();
+    moved.outline.as_mut().unwrap()[4][0].a = At(50., 700.);
+    let newxml = glifparser::glif::write(&moved).unwrap();
+    assert!(newxml.contains(concat!(
+        r#"<point x="50" y="700" name="single"/>"#, "\n      ",
+        r#"<point x="200" y="700"/>"#, "\n      ",
+        r#"<point x="300" y="600" type="curve"/>"#)));
+}
+
+#[test]
+fn test_bad_contours() {
+    let bad_contours = [
+        // A curve has at most two off-curve points
+        r#"<point x="0" y="0" type="line"/><point x="1" y="1"/><point x="2" y="2"/><point x="3" y="3"/><point x="4" y="4" type="curve"/>"#,
+        // A line has none
+        r#"<point x="0" y="0" type="line"/><point x="1" y="1"/><point x="4" y="4" type="line"/>"#,
+        r#"<point x="0" y="0" type="line"/><point x="4" y="4" type="move"/>"#,
+        r#"<point x="0" y="0" type="move"/><point x="4" y="4" type="line"/><point x="1" y="1"/>"#,
+    ];
+    for contour in bad_contours {
+        let glif = format!(r#"<glyph name="bad" format="2"><outline><contour>{}</contour></outline></glyph>"#, contour);
+        assert!(glifparser::glif::read::<()>(&glif).is_err(), "{}", contour);
+
+        let (glif, diagnostics) = glifparser::glif::read_with_diagnostics::<()>(&glif, Default::default());
+        assert!(glif.unwrap().outline.is_none());
+        assert_eq!(diagnostics.last().unwrap().location.path.last(), Some(&(String::from("contour"), 0)));
+    }
+}
@@ -40,3 +40,49 @@ fn test_rev_contour() {
     }
     assert_ne!(gcircle_contour, gcircle2_contour);
 }
+
+#[test]
+fn test_rev_open_contour() {
+    use glifparser::outline::{GlifContour, GlifOutline};
+    use glifparser::point::{GlifPoint, PointType};
+
+    // move, line, off, off, curve, line
+    let gpoint = |x: i32, y: i32, ptype| GlifPoint::from_x_y_type((x, y), ptype);
+    let gcontour = GlifContour::from(vec![
+        gpoint(0, 0, PointType::Move), gpoint(0, 100, PointType::Line), gpoint(50, 150, PointType::OffCurve),
+        gpoint(100, 150, PointType::OffCurve), gpoint(150, 100, PointType::Curve), gpoint(150, 0, PointType::Line),
+    ]);
+    let outline: Outline<()> = GlifOutline::from(vec![gcontour]).try_into().unwrap();
+    let reversed = outline.to_reversed();
+    let ptypes: Vec<PointType> = reversed[0].iter().map(|p| p.ptype).collect();
+    assert_eq!(ptypes, [PointType::Move, PointType::Line, PointType::Curve, PointType::Line]);
+    assert_eq!((reversed[0][1].x, reversed[0][1].a), (150., glifparser::Handle::At(100., 150.)));
+    assert_eq!((reversed[0][2].x, reversed[0][2].b), (0., glifparser::Handle::At(50., 150.)));
+    assert_eq!(reversed.to_reversed(), outline);
+}
+
+#[test]
+fn test_rev_contour_keeps_identifier_and_lib() {
+    use glifparser::outline::{CorrectDirection as _, DirectionConvention};
+    use glifparser::point::{Point, PointType};
+
+    let mut contour: Contour<()> = [(0., 0.), (0., 100.), (100., 100.), (100., 0.)].iter().map(|&xy| Point::from_x_y_type(xy, PointType::Line)).collect();
+    contour.identifier = Some(String::from("square"));
+    contour.lib.insert(String::from("com.example.kept"), true.into());
+    let reversed = contour.to_reversed();
+    assert_eq!((&reversed.identifier, &reversed.lib), (&contour.identifier, &contour.lib));
+    assert_ne!(reversed.points, contour.points);
+
+    // Nor are they lost from the .glif when correcting the direction reverses the contour
+    let mut outline: Outline<()> = vec![contour];
+    outline.correct_direction(DirectionConvention::PostScript);
+    assert_eq!(outline[0].lib.get("com.example.kept"), Some(&true.into()));
+    #[cfg(feature = "glifserde")]
+    {
+        let mut glif = glifparser::Glif::<()>::new();
+        glif.name = String::from("square");
+        glif.outline = Some(outline);
+        let read: glifparser::Glif<()> = glifparser::glif::read(&glifparser::glif::write(&glif).unwrap()).unwrap();
+        assert_eq!(read.outline.unwrap()[0].lib.get("com.example.kept"), Some(&true.into()));
+    }
+}
@@ -0,0 +1,31 @@
+use glifparser::glif::GlifHeader;
+
+#[test]
+fn test_scan() {
+    let header = glifparser::glif::scan_from_filename("test_data/TT2020Base.ufo/glyphs/acute.glif").unwrap();
+    let glif: glifparser::Glif<()> = glifparser::glif::read_from_filename("test_data/TT2020Base.ufo/glyphs/acute.glif").unwrap();
+    assert_eq!(header, GlifHeader {
+        name: glif.name.clone(),
+        unicode: glif.unicode.clone(),
+        width: glif.width,
+        height: glif.height,
+        component_bases: glif.components.vec.iter().map(|c| c.base.clone()).collect(),
+    });
+
+    let composite = r#"<glyph name="Aacute" format="2">
+  <outline>
+    <contour><point x="oops" y="0" type="line"/></contour>
+    <component base="A"/>
+    <component base="acute" xOffset="100"/>
+  </outline>
+  <unicode hex="00C1"/>
+  <lib><dict><key>unparsed</key></dict></lib>
+</glyph>"#;
+    let header = glifparser::glif::scan(composite).unwrap();
+    assert_eq!(header.unicode, vec!['Á']);
+    assert_eq!(header.component_bases, vec!["A", "acute"]);
+
+    let missing = "test_data/TT2020Base.ufo/glyphs/missing.glif";
+    let error = glifparser::glif::scan_from_filename(missing).unwrap_err();
+    assert_eq!(error.location().and_then(|l| l.filename.as_deref()), Some(std::path::Path::new(missing)));
+}
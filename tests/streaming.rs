@@ -0,0 +1,41 @@
+static GLIF: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<!-- comment -->
+<glyph name="a&amp;b" format="2">
+  <unknown><point x="0" y="0" type="line" /></unknown>
+  <advance width="10" />
+  <advance width="20" />
+  <note>  hello <b>there</b> &lt;world&gt;
+  </note>
+  <outline>
+    <component base="c" />
+    <contour><point x="1" y="2" type="line"></point><point x="3" y="4" type="line" /></contour>
+  </outline>
+  <outline><contour><point x="9" y="9" type="line" /></contour></outline>
+</glyph>
+<!-- trailing comment -->
+"#;
+
+#[test]
+fn test_streaming_read() {
+    let glif: glifparser::Glif<()> = glifparser::glif::read(GLIF).unwrap();
+    assert_eq!(glif.name, "a&b");
+    assert_eq!(glif.width, Some(10));
+    assert_eq!(glif.note.as_deref(), Some("  hello  <world>\n  "));
+    let outline = glif.outline.unwrap();
+    assert_eq!(outline.len(), 1);
+    assert_eq!(outline[0].len(), 2);
+    assert_eq!(glif.components.vec[0].base, "c");
+
+    // XML has a single root element
+    let err = glifparser::glif::read::<()>(&format!("{}<glyph name=\"b\" format=\"2\"/>", GLIF)).unwrap_err();
+    assert_eq!(err.location().unwrap().line, Some(16));
+    assert!(glifparser::glif::read::<()>(&format!("{}text", GLIF)).is_err());
+}
+
+#[test]
+fn test_streaming_truncated() {
+    let truncated = &GLIF[..GLIF.find("</contour>").unwrap()];
+    let err = glifparser::glif::read::<()>(truncated).unwrap_err();
+    let location = err.location().unwrap();
+    assert_eq!(location.path, vec![(String::from("outline"), 0), (String::from("contour"), 0)]);
+}
@@ -0,0 +1,29 @@
+static GLIF: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<glyph name="uni4E00" format="2">
+  <advance width="1000" height="1000" />
+  <unicode hex="4E00" />
+  <lib>
+    <dict>
+      <key>public.verticalOrigin</key>
+      <integer>880</integer>
+    </dict>
+  </lib>
+</glyph>"#;
+
+#[test]
+fn test_vertical_metrics() {
+    let mut glif: glifparser::Glif<()> = glifparser::glif::read(GLIF).unwrap();
+    assert_eq!((glif.width, glif.height), (Some(1000), Some(1000)));
+    // <lib> is only read with plist's serde support
+    #[cfg(feature = "glifserde")]
+    assert_eq!(glif.lib.vertical_origin(), Some(880.));
+
+    glif.width = None;
+    glif.lib.set_vertical_origin(Some(900.5));
+    let newxml = glifparser::glif::write(&glif).unwrap();
+    assert!(newxml.contains(r#"<advance height="1000""#));
+    let newglif: glifparser::Glif<()> = glifparser::glif::read(&newxml).unwrap();
+    assert_eq!((newglif.width, newglif.height), (None, Some(1000)));
+    #[cfg(feature = "glifserde")]
+    assert_eq!(newglif.lib.vertical_origin(), Some(900.5));
+}